@@ -71,6 +71,7 @@ pub fn claim_handler(ctx: Context<ClaimAirdrop>, index: u64, leaf: [u8;32], proo
         staking_fee: 0,
         protocol_fee: 0,
         referral_fee: 0,
+        launch_fee: 0,
     });
 
     Ok(())
@@ -168,6 +169,7 @@ pub fn process_expiry_handler(ctx: Context<ProcessAirdropExpiry>, total_unclaime
         staking_fee: 0,
         protocol_fee: 0,
         referral_fee: 0,
+        launch_fee: 0,
     });
 
     Ok(())
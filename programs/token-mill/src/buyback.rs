@@ -35,7 +35,10 @@ pub struct PerformBuyback<'info> {
 }
 pub fn handler(ctx: Context<PerformBuyback>, lamports: u64, swap_ix: Option<Vec<u8>>) -> Result<()> {
     // If a swap instruction payload is provided, forward it as a CPI signed by market PDA.
-    let market = ctx.accounts.market.load()?;
+    let mut market = ctx.accounts.market.load_mut()?;
+    market.assert_not_paused(&ctx.accounts.config)?;
+    // Buybacks are funded by the launch fees routed to the buyback pool
+    market.spend_buyback_fees(lamports)?;
     let bump = market.bump;
     let signer_seeds: &[&[u8]] = &[
         crate::state::MARKET_PDA_SEED.as_bytes(),
@@ -65,12 +68,14 @@ pub fn handler(ctx: Context<PerformBuyback>, lamports: u64, swap_ix: Option<Vec<
         }
     }
 
-    // Fallback: simulate swap by converting lamports -> tokens using market.ask_prices[0]
-    let price = market.ask_prices[0];
-    if price == 0 { return Err(error!(TokenMillError::InvalidPrice)); }
+    // Fallback: simulate swap by converting lamports -> tokens along the market's ask curve
+    let (tokens_bought, _, _) = crate::curve::get_swap_amounts(&market, crate::SwapType::Buy, crate::SwapAmountType::ExactInput, lamports)?;
 
-    // tokens_bought = lamports / price
-    let tokens_bought = lamports.checked_div(price).ok_or(error!(TokenMillError::MathOverflow))?;
+    // The bought tokens leave the curve, backed by the buyback fees left in the market
+    if !market.is_lamport_market() {
+        market.base_reserve = market.base_reserve.checked_sub(tokens_bought).ok_or(error!(TokenMillError::InvalidAmount))?;
+    }
+    market.increase_circulating_supply(tokens_bought)?;
 
     // Update reflection pool
     let mut reflection = ctx.accounts.reflection_state.load_mut()?;
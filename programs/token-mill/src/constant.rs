@@ -1,2 +1,11 @@
 pub const PRICES_LENGTH: usize = 11;
 pub const MILL_TOKEN_DECIMALS: u8 = 6;
+pub const BPS_DENOMINATOR: u16 = 10_000;
+/// Highest curve price allowed, in whole quote tokens per whole base token
+pub const MAX_PRICE: u64 = 1_000_000;
+/// Default delay before fee share changes take effect on a live market, in seconds
+pub const DEFAULT_FEE_SHARE_NOTICE_PERIOD: i64 = 86_400;
+/// Highest protocol fee discount a discount tier can grant, in basis points
+pub const MAX_DISCOUNT_BPS: u16 = 5_000;
+/// Highest launch fee a market can start with, in basis points
+pub const MAX_LAUNCH_FEE_BPS: u16 = 5_000;
@@ -0,0 +1,81 @@
+//! Pump-style constant-product curve. The market trades like an `x * y = k` pool whose base
+//! reserve is `virtual_base_reserve - supply`, with `k` fixed by the virtual reserves at zero
+//! circulating supply. The virtual reserves are never backed by actual tokens.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::MILL_TOKEN_DECIMALS, curve::math::mul_div, errors::TokenMillError, state::MarketCurve,
+};
+
+fn invariant(curve: &MarketCurve) -> u128 {
+    curve.virtual_base_reserve as u128 * curve.virtual_quote_reserve as u128
+}
+
+fn base_reserve(curve: &MarketCurve, supply: u64) -> Result<u128> {
+    curve
+        .virtual_base_reserve
+        .checked_sub(supply)
+        .filter(|reserve| *reserve > 0)
+        .map(u128::from)
+        .ok_or(error!(TokenMillError::InvalidAmount))
+}
+
+/// Virtual quote reserve of the pool once `supply` base tokens are circulating.
+fn quote_reserve(curve: &MarketCurve, supply: u64, round_up: bool) -> Result<u128> {
+    let base_reserve = base_reserve(curve, supply)?;
+    let k = invariant(curve);
+    Ok(if round_up {
+        k.div_ceil(base_reserve)
+    } else {
+        k / base_reserve
+    })
+}
+
+/// Marginal price at `supply`, `k / base_reserve^2` scaled to a whole base token.
+pub fn price_at(curve: &MarketCurve, supply: u64, round_up: bool) -> Result<u64> {
+    let base_reserve = base_reserve(curve, supply)?;
+    let price = mul_div(
+        invariant(curve),
+        10u128.pow(MILL_TOKEN_DECIMALS as u32),
+        base_reserve * base_reserve,
+        round_up,
+    )?;
+
+    u64::try_from(price).map_err(|_| error!(TokenMillError::MathOverflow))
+}
+
+/// Quote amount needed to move the circulating supply from `from` to `to` along the curve.
+pub fn integral(curve: &MarketCurve, from: u64, to: u64, round_up: bool) -> Result<u64> {
+    if from > to {
+        return Err(error!(TokenMillError::InvalidAmount));
+    }
+
+    let end = quote_reserve(curve, to, round_up)?;
+    let start = quote_reserve(curve, from, !round_up)?;
+
+    u64::try_from(end.saturating_sub(start)).map_err(|_| error!(TokenMillError::MathOverflow))
+}
+
+/// Largest base amount bought from `supply` for at most `quote_amount`. May exceed the remaining
+/// supply.
+pub fn base_for_quote_in(curve: &MarketCurve, supply: u64, quote_amount: u64) -> Result<u64> {
+    let target = quote_reserve(curve, supply, false)?
+        .checked_add(quote_amount as u128)
+        .ok_or(error!(TokenMillError::MathOverflow))?;
+    let new_base_reserve = invariant(curve).div_ceil(target);
+
+    Ok(base_reserve(curve, supply)?.saturating_sub(new_base_reserve) as u64)
+}
+
+/// Smallest base amount to sell from `supply` to receive at least `quote_amount`.
+pub fn base_for_quote_out(curve: &MarketCurve, supply: u64, quote_amount: u64) -> Result<u64> {
+    let target = quote_reserve(curve, supply, false)?
+        .checked_sub(quote_amount as u128)
+        .filter(|target| *target > 0)
+        .ok_or(error!(TokenMillError::InvalidAmount))?;
+    let new_base_reserve = invariant(curve).div_ceil(target);
+
+    u64::try_from(new_base_reserve.saturating_sub(base_reserve(curve, supply)?))
+        .map_err(|_| error!(TokenMillError::InvalidAmount))
+}
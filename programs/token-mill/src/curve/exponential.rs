@@ -0,0 +1,152 @@
+//! Exponential curve. The bid price grows from `initial_price` at zero circulating supply to
+//! `final_price` at the total supply:
+//! `price(s) = initial_price * exp(k * s / total_supply)` with `k = ln(final_price / initial_price)`.
+//! Prices are expressed in quote units per whole base token.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::MILL_TOKEN_DECIMALS,
+    curve::math::{exp_wad, ln_wad, mul_div, MAX_EXPONENT_WAD, WAD},
+    errors::TokenMillError,
+    state::MarketCurve,
+};
+
+/// Growth exponent `k` of the curve, in WAD.
+pub fn growth_rate(initial_price: u64, final_price: u64) -> Result<u128> {
+    if initial_price == 0 || final_price < initial_price {
+        return Err(error!(TokenMillError::InvalidCurveParameters));
+    }
+
+    let ratio = mul_div(final_price as u128, WAD, initial_price as u128, false)?;
+    let rate = ln_wad(ratio)?;
+    if rate > MAX_EXPONENT_WAD {
+        return Err(error!(TokenMillError::InvalidCurveParameters));
+    }
+
+    Ok(rate)
+}
+
+/// Bid price at `supply`.
+pub fn price_at(
+    curve: &MarketCurve,
+    total_supply: u64,
+    supply: u64,
+    round_up: bool,
+) -> Result<u64> {
+    let rate = growth_rate(curve.initial_price, curve.final_price)?;
+    let growth = exp_wad(mul_div(
+        rate,
+        supply as u128,
+        total_supply as u128,
+        round_up,
+    )?)?;
+    let price = mul_div(curve.initial_price as u128, growth, WAD, round_up)?;
+
+    u64::try_from(price).map_err(|_| error!(TokenMillError::MathOverflow))
+}
+
+/// Quote amount needed to move the circulating supply from `from` to `to` along the bid curve.
+pub fn integral(
+    curve: &MarketCurve,
+    total_supply: u64,
+    from: u64,
+    to: u64,
+    round_up: bool,
+) -> Result<u64> {
+    if from > to || to > total_supply {
+        return Err(error!(TokenMillError::InvalidAmount));
+    }
+
+    let rate = growth_rate(curve.initial_price, curve.final_price)?;
+    let initial_price = curve.initial_price as u128;
+    let base_unit = 10u128.pow(MILL_TOKEN_DECIMALS as u32);
+
+    let amount = if rate == 0 {
+        mul_div(initial_price, (to - from) as u128, base_unit, round_up)?
+    } else {
+        // initial_price * total_supply / k * (exp(k * to / total_supply) - exp(k * from / total_supply))
+        let end = exp_wad(mul_div(rate, to as u128, total_supply as u128, round_up)?)?;
+        let start = exp_wad(mul_div(
+            rate,
+            from as u128,
+            total_supply as u128,
+            !round_up,
+        )?)?;
+        mul_div(
+            initial_price * total_supply as u128,
+            end.saturating_sub(start),
+            rate * base_unit,
+            round_up,
+        )?
+    };
+
+    u64::try_from(amount).map_err(|_| error!(TokenMillError::MathOverflow))
+}
+
+/// Approximate base amount bought from `supply` for `quote_amount` on the bid curve, rounded
+/// down. May exceed the remaining supply.
+pub fn base_for_quote_in(
+    curve: &MarketCurve,
+    total_supply: u64,
+    supply: u64,
+    quote_amount: u64,
+) -> Result<u64> {
+    let rate = growth_rate(curve.initial_price, curve.final_price)?;
+    let initial_price = curve.initial_price as u128;
+    let quote_scaled = quote_amount as u128 * 10u128.pow(MILL_TOKEN_DECIMALS as u32);
+
+    let base_amount = if rate == 0 {
+        quote_scaled / initial_price
+    } else {
+        // exp(k * new_supply / total_supply) grows by quote * k / (initial_price * total_supply)
+        let start = exp_wad(mul_div(rate, supply as u128, total_supply as u128, true)?)?;
+        let increase = mul_div(
+            quote_scaled,
+            rate,
+            initial_price * total_supply as u128,
+            false,
+        )?;
+        let target = start
+            .checked_add(increase)
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+        let new_supply = mul_div(ln_wad(target)?, total_supply as u128, rate, false)?;
+        new_supply.saturating_sub(supply as u128)
+    };
+
+    u64::try_from(base_amount).map_err(|_| error!(TokenMillError::MathOverflow))
+}
+
+/// Approximate base amount to sell from `supply` to receive `quote_amount` on the bid curve,
+/// rounded up.
+pub fn base_for_quote_out(
+    curve: &MarketCurve,
+    total_supply: u64,
+    supply: u64,
+    quote_amount: u64,
+) -> Result<u64> {
+    let rate = growth_rate(curve.initial_price, curve.final_price)?;
+    let initial_price = curve.initial_price as u128;
+    let quote_scaled = quote_amount as u128 * 10u128.pow(MILL_TOKEN_DECIMALS as u32);
+
+    let base_amount = if rate == 0 {
+        quote_scaled.div_ceil(initial_price)
+    } else {
+        let start = exp_wad(mul_div(rate, supply as u128, total_supply as u128, false)?)?;
+        let decrease = mul_div(
+            quote_scaled,
+            rate,
+            initial_price * total_supply as u128,
+            true,
+        )?;
+        // The market cannot pay out more than the whole area under the curve below `supply`
+        let target = start
+            .checked_sub(decrease)
+            .filter(|target| *target >= WAD)
+            .ok_or(error!(TokenMillError::InvalidAmount))?;
+        let new_supply = mul_div(ln_wad(target)?, total_supply as u128, rate, true)?;
+        (supply as u128).saturating_sub(new_supply)
+    };
+
+    u64::try_from(base_amount).map_err(|_| error!(TokenMillError::InvalidAmount))
+}
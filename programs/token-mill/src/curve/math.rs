@@ -0,0 +1,109 @@
+//! Fixed-point helpers for the curves that need more than linear arithmetic. Fractional values
+//! are scaled by `WAD` (1e18).
+
+use anchor_lang::prelude::*;
+
+use crate::errors::TokenMillError;
+
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+const LN_2_WAD: u128 = 693_147_180_559_945_309;
+/// Largest exponent accepted by `exp_wad`, keeping its result well within `u128`
+pub const MAX_EXPONENT_WAD: u128 = 40 * WAD;
+
+const LOW_MASK: u128 = u64::MAX as u128;
+
+/// `a * b / denominator` with a 256-bit intermediate product.
+pub fn mul_div(a: u128, b: u128, denominator: u128, round_up: bool) -> Result<u128> {
+    if denominator == 0 {
+        return Err(error!(TokenMillError::MathError));
+    }
+
+    let (a_high, a_low) = (a >> 64, a & LOW_MASK);
+    let (b_high, b_low) = (b >> 64, b & LOW_MASK);
+    let low_low = a_low * b_low;
+    let low_high = a_low * b_high;
+    let high_low = a_high * b_low;
+    let middle = (low_low >> 64) + (low_high & LOW_MASK) + (high_low & LOW_MASK);
+    let low = (low_low & LOW_MASK) | (middle << 64);
+    let high = a_high * b_high + (low_high >> 64) + (high_low >> 64) + (middle >> 64);
+
+    let (quotient, remainder) = if high == 0 {
+        (low / denominator, low % denominator)
+    } else {
+        if high >= denominator {
+            return Err(error!(TokenMillError::MathOverflow));
+        }
+
+        // Long division of the 256-bit product, one bit of the low half at a time
+        let (mut quotient, mut remainder) = (0u128, high);
+        for i in (0..128).rev() {
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | ((low >> i) & 1);
+            quotient <<= 1;
+            if carry == 1 || remainder >= denominator {
+                remainder = remainder.wrapping_sub(denominator);
+                quotient |= 1;
+            }
+        }
+        (quotient, remainder)
+    };
+
+    if round_up && remainder != 0 {
+        quotient
+            .checked_add(1)
+            .ok_or(error!(TokenMillError::MathOverflow))
+    } else {
+        Ok(quotient)
+    }
+}
+
+/// `exp(x)` for `x` in WAD, rounded down.
+pub fn exp_wad(x: u128) -> Result<u128> {
+    if x > MAX_EXPONENT_WAD {
+        return Err(error!(TokenMillError::MathOverflow));
+    }
+
+    // exp(x) = 2^n * exp(r) with r = x - n * ln(2) in [0, ln(2))
+    let n = x / LN_2_WAD;
+    let r = x - n * LN_2_WAD;
+
+    let (mut term, mut sum) = (WAD, WAD);
+    let mut i = 1;
+    while term != 0 {
+        term = term * r / (i * WAD);
+        sum += term;
+        i += 1;
+    }
+
+    Ok(sum << n)
+}
+
+/// `ln(y)` for `y >= 1` in WAD, rounded down.
+pub fn ln_wad(y: u128) -> Result<u128> {
+    if y < WAD {
+        return Err(error!(TokenMillError::MathError));
+    }
+
+    // ln(y) = n * ln(2) + ln(m) with m = y / 2^n in [1, 2)
+    let (mut n, mut m) = (0u128, y);
+    while m >= 2 * WAD {
+        m >>= 1;
+        n += 1;
+    }
+
+    // ln(m) = 2 * atanh(z) with z = (m - 1) / (m + 1) in [0, 1/3)
+    let z = (m - WAD) * WAD / (m + WAD);
+    let z_squared = z * z / WAD;
+    let (mut term, mut sum) = (z, z);
+    let mut i = 3;
+    loop {
+        term = term * z_squared / WAD;
+        if term == 0 {
+            break;
+        }
+        sum += term / i;
+        i += 2;
+    }
+
+    Ok(n * LN_2_WAD + 2 * sum)
+}
@@ -0,0 +1,240 @@
+//! Bonding curves shared by every instruction that prices a trade.
+//!
+//! Each market picks a curve family at creation, recorded in `Market::curve_kind`. Buys are
+//! priced on the ask side of the curve and sells on the bid side. The price-array curve has
+//! its own ask prices, while the exponential and constant-product curves add `spread_bps` on
+//! top of their bid side. Rounding always favours the market.
+
+pub mod constant_product;
+pub mod exponential;
+pub mod math;
+pub mod price_array;
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::BPS_DENOMINATOR,
+    errors::TokenMillError,
+    state::{CurveKind, Market},
+    SwapAmountType, SwapType,
+};
+
+/// Returns `(base_amount, quote_amount, fee)` for a swap, where `fee` is the ask/bid spread
+/// paid on buys (always 0 on sells).
+pub fn get_swap_amounts(
+    market: &Market,
+    swap_type: SwapType,
+    swap_amount_type: SwapAmountType,
+    amount: u64,
+) -> Result<(u64, u64, u64)> {
+    let is_configured = match market.curve_kind()? {
+        CurveKind::PriceArray => market.width_scaled != 0,
+        CurveKind::Exponential | CurveKind::ConstantProduct => market.total_supply != 0,
+    };
+    if !is_configured {
+        return Err(error!(TokenMillError::InvalidMarketState));
+    }
+    let supply = market.circulating_supply;
+
+    match swap_type {
+        SwapType::Buy => {
+            let base_amount = match swap_amount_type {
+                SwapAmountType::ExactInput => base_for_quote_in(market, supply, amount)?,
+                SwapAmountType::ExactOutput => amount,
+            };
+            if base_amount == 0 {
+                return Err(error!(TokenMillError::InvalidAmount));
+            }
+            let new_supply = supply
+                .checked_add(base_amount)
+                .ok_or(error!(TokenMillError::MathOverflow))?;
+            if new_supply > market.total_supply {
+                return Err(error!(TokenMillError::InvalidTotalSupply));
+            }
+
+            let quote_amount = ask_integral(market, supply, new_supply)?;
+            let bid_amount = bid_integral(market, supply, new_supply)?;
+            let fee = quote_amount.saturating_sub(bid_amount);
+
+            Ok((base_amount, quote_amount, fee))
+        }
+        SwapType::Sell => {
+            let (base_amount, quote_amount) = match swap_amount_type {
+                SwapAmountType::ExactInput => {
+                    let new_supply = supply
+                        .checked_sub(amount)
+                        .ok_or(error!(TokenMillError::InvalidAmount))?;
+                    (amount, bid_integral(market, new_supply, supply)?)
+                }
+                SwapAmountType::ExactOutput => {
+                    (base_for_quote_out(market, supply, amount)?, amount)
+                }
+            };
+            if quote_amount == 0 {
+                return Err(error!(TokenMillError::InvalidAmount));
+            }
+
+            Ok((base_amount, quote_amount, 0))
+        }
+    }
+}
+
+/// Quote amount paid by buyers to move the circulating supply from `from` to `to`, rounded up.
+pub fn ask_integral(market: &Market, from: u64, to: u64) -> Result<u64> {
+    match market.curve_kind()? {
+        CurveKind::PriceArray => {
+            price_array::price_integral(&market.ask_prices, market.width_scaled, from, to, true)
+        }
+        CurveKind::Exponential => add_spread(
+            exponential::integral(&market.curve, market.total_supply, from, to, true)?,
+            market.curve.spread_bps,
+        ),
+        CurveKind::ConstantProduct => add_spread(
+            constant_product::integral(&market.curve, from, to, true)?,
+            market.curve.spread_bps,
+        ),
+    }
+}
+
+/// Quote amount paid to sellers moving the circulating supply from `to` back to `from`,
+/// rounded down.
+pub fn bid_integral(market: &Market, from: u64, to: u64) -> Result<u64> {
+    match market.curve_kind()? {
+        CurveKind::PriceArray => {
+            price_array::price_integral(&market.bid_prices, market.width_scaled, from, to, false)
+        }
+        CurveKind::Exponential => {
+            exponential::integral(&market.curve, market.total_supply, from, to, false)
+        }
+        CurveKind::ConstantProduct => constant_product::integral(&market.curve, from, to, false),
+    }
+}
+
+/// Largest base amount that can be bought from `supply` for at most `quote_amount`.
+pub fn base_for_quote_in(market: &Market, supply: u64, quote_amount: u64) -> Result<u64> {
+    let curve = &market.curve;
+    // The bid side is inverted on the quote left once the spread is removed
+    let budget = (quote_amount as u128 * BPS_DENOMINATOR as u128
+        / (BPS_DENOMINATOR as u128 + curve.spread_bps as u128)) as u64;
+
+    let estimate = match market.curve_kind()? {
+        CurveKind::PriceArray => {
+            return price_array::base_for_quote_in(
+                &market.ask_prices,
+                market.width_scaled,
+                supply,
+                quote_amount,
+            )
+        }
+        CurveKind::Exponential => {
+            exponential::base_for_quote_in(curve, market.total_supply, supply, budget)?
+        }
+        CurveKind::ConstantProduct => constant_product::base_for_quote_in(curve, supply, budget)?,
+    };
+
+    // Settle the rounding of the closed-form inverse against the ask side itself
+    let max_base_amount = market.total_supply.saturating_sub(supply);
+    last_matching(0, max_base_amount, estimate, |base_amount| {
+        Ok(ask_integral(market, supply, supply + base_amount)? <= quote_amount)
+    })
+}
+
+/// Smallest base amount that must be sold from `supply` to receive at least `quote_amount`.
+pub fn base_for_quote_out(market: &Market, supply: u64, quote_amount: u64) -> Result<u64> {
+    let curve = &market.curve;
+    let estimate = match market.curve_kind()? {
+        CurveKind::PriceArray => {
+            return price_array::base_for_quote_out(
+                &market.bid_prices,
+                market.width_scaled,
+                supply,
+                quote_amount,
+            )
+        }
+        CurveKind::Exponential => {
+            exponential::base_for_quote_out(curve, market.total_supply, supply, quote_amount)?
+        }
+        CurveKind::ConstantProduct => {
+            constant_product::base_for_quote_out(curve, supply, quote_amount)?
+        }
+    };
+
+    // Settle the rounding of the closed-form inverse against the bid side itself, as one more
+    // than the largest amount that still falls short of `quote_amount`
+    if quote_amount == 0 {
+        return Ok(0);
+    }
+    if bid_integral(market, 0, supply)? < quote_amount {
+        return Err(error!(TokenMillError::InvalidAmount));
+    }
+    let short_amount = last_matching(0, supply, estimate.saturating_sub(1), |base_amount| {
+        Ok(bid_integral(market, supply - base_amount, supply)? < quote_amount)
+    })?;
+
+    Ok(short_amount + 1)
+}
+
+/// Binary searches the largest value of `[low, high]` matching `is_match`, which must hold at
+/// `low` and stop holding past some point. `hint` is probed first, so an accurate estimate
+/// settles the search in two steps.
+fn last_matching(
+    mut low: u64,
+    mut high: u64,
+    hint: u64,
+    is_match: impl Fn(u64) -> Result<bool>,
+) -> Result<u64> {
+    let hint = hint.clamp(low, high);
+    if is_match(hint)? {
+        low = hint;
+        if hint < high && !is_match(hint + 1)? {
+            return Ok(hint);
+        }
+    } else {
+        high = hint.saturating_sub(1).max(low);
+    }
+
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+        if is_match(mid)? {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    Ok(low)
+}
+
+/// Marginal price at `supply` on the side of the curve used by `swap_type`, in quote units per
+/// whole base token.
+pub fn spot_price(market: &Market, swap_type: SwapType, supply: u64) -> Result<u64> {
+    let curve = &market.curve;
+    let round_up = swap_type == SwapType::Buy;
+
+    let bid_price = match market.curve_kind()? {
+        CurveKind::PriceArray => {
+            let prices = match swap_type {
+                SwapType::Buy => &market.ask_prices,
+                SwapType::Sell => &market.bid_prices,
+            };
+            let price = price_array::price_at(prices, market.width_scaled, supply, round_up)?;
+            return u64::try_from(price).map_err(|_| error!(TokenMillError::MathOverflow));
+        }
+        CurveKind::Exponential => {
+            exponential::price_at(curve, market.total_supply, supply, round_up)?
+        }
+        CurveKind::ConstantProduct => constant_product::price_at(curve, supply, round_up)?,
+    };
+
+    match swap_type {
+        SwapType::Buy => add_spread(bid_price, curve.spread_bps),
+        SwapType::Sell => Ok(bid_price),
+    }
+}
+
+fn add_spread(amount: u64, spread_bps: u16) -> Result<u64> {
+    let amount = (amount as u128 * (BPS_DENOMINATOR as u128 + spread_bps as u128))
+        .div_ceil(BPS_DENOMINATOR as u128);
+
+    u64::try_from(amount).map_err(|_| error!(TokenMillError::MathOverflow))
+}
@@ -0,0 +1,170 @@
+//! Piecewise-linear curve. It is described by `PRICES_LENGTH` price points evenly spaced every
+//! `width_scaled` base tokens of circulating supply, set by the creator through
+//! `set_market_prices`. Prices are expressed in quote units per whole base token, and the price
+//! between two points is linearly interpolated. Separate bid and ask price points are used.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    constant::{MILL_TOKEN_DECIMALS, PRICES_LENGTH},
+    errors::TokenMillError,
+};
+
+/// Price of the curve at `supply`, in quote units per whole base token.
+pub fn price_at(
+    prices: &[u64; PRICES_LENGTH],
+    width: u64,
+    supply: u64,
+    round_up: bool,
+) -> Result<u128> {
+    let index = (supply / width) as usize;
+    if index >= PRICES_LENGTH - 1 {
+        return Ok(prices[PRICES_LENGTH - 1] as u128);
+    }
+
+    let offset = (supply % width) as u128;
+    let start = prices[index] as u128;
+    let delta = (prices[index + 1] as u128)
+        .checked_sub(start)
+        .ok_or(error!(TokenMillError::DecreasingPrices))?;
+
+    let numerator = delta
+        .checked_mul(offset)
+        .ok_or(error!(TokenMillError::MathOverflow))?;
+    let width = width as u128;
+    let increment = if round_up {
+        numerator.div_ceil(width)
+    } else {
+        numerator / width
+    };
+
+    Ok(start + increment)
+}
+
+/// Quote amount needed to move the circulating supply from `from` to `to` along `prices`.
+/// Each segment of the piecewise-linear curve is integrated with the trapezoidal rule.
+pub fn price_integral(
+    prices: &[u64; PRICES_LENGTH],
+    width: u64,
+    from: u64,
+    to: u64,
+    round_up: bool,
+) -> Result<u64> {
+    let max_supply = width
+        .checked_mul((PRICES_LENGTH - 1) as u64)
+        .ok_or(error!(TokenMillError::MathOverflow))?;
+    if from > to || to > max_supply {
+        return Err(error!(TokenMillError::InvalidAmount));
+    }
+
+    let mut numerator: u128 = 0;
+    let mut lower = from;
+    while lower < to {
+        let segment_end = (lower / width + 1) * width;
+        let upper = segment_end.min(to);
+
+        let start_price = price_at(prices, width, lower, round_up)?;
+        // The end of a segment is priced with that segment's slope
+        let end_price = if upper == segment_end {
+            prices[(lower / width + 1) as usize] as u128
+        } else {
+            price_at(prices, width, upper, round_up)?
+        };
+
+        let area = ((upper - lower) as u128)
+            .checked_mul(start_price + end_price)
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+        numerator = numerator
+            .checked_add(area)
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+
+        lower = upper;
+    }
+
+    let denominator = 2 * 10u128.pow(MILL_TOKEN_DECIMALS as u32);
+    let amount = if round_up {
+        numerator.div_ceil(denominator)
+    } else {
+        numerator / denominator
+    };
+
+    u64::try_from(amount).map_err(|_| error!(TokenMillError::MathOverflow))
+}
+
+/// Largest base amount that can be bought from `supply` for at most `quote_amount`.
+pub fn base_for_quote_in(
+    prices: &[u64; PRICES_LENGTH],
+    width: u64,
+    supply: u64,
+    quote_amount: u64,
+) -> Result<u64> {
+    let max_supply = width
+        .checked_mul((PRICES_LENGTH - 1) as u64)
+        .ok_or(error!(TokenMillError::MathOverflow))?;
+
+    let mut remaining = quote_amount;
+    let mut current = supply;
+    while current < max_supply {
+        let segment_end = (current / width + 1) * width;
+        let segment_cost = price_integral(prices, width, current, segment_end, true)?;
+        if segment_cost <= remaining {
+            remaining -= segment_cost;
+            current = segment_end;
+            continue;
+        }
+
+        // Binary search the largest amount affordable within this segment
+        let (mut low, mut high) = (0u64, segment_end - current);
+        while low < high {
+            let mid = low + (high - low).div_ceil(2);
+            if price_integral(prices, width, current, current + mid, true)? <= remaining {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        current += low;
+        break;
+    }
+
+    Ok(current - supply)
+}
+
+/// Smallest base amount that must be sold from `supply` to receive at least `quote_amount`.
+pub fn base_for_quote_out(
+    prices: &[u64; PRICES_LENGTH],
+    width: u64,
+    supply: u64,
+    quote_amount: u64,
+) -> Result<u64> {
+    let mut remaining = quote_amount;
+    let mut current = supply;
+    while remaining > 0 {
+        if current == 0 {
+            return Err(error!(TokenMillError::InvalidAmount));
+        }
+
+        let segment_start = (current - 1) / width * width;
+        let segment_value = price_integral(prices, width, segment_start, current, false)?;
+        if segment_value < remaining {
+            remaining -= segment_value;
+            current = segment_start;
+            continue;
+        }
+
+        // Binary search the smallest amount covering the remaining quote within this segment
+        let (mut low, mut high) = (1u64, current - segment_start);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if price_integral(prices, width, current - mid, current, false)? >= remaining {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        current -= low;
+        break;
+    }
+
+    Ok(supply - current)
+}
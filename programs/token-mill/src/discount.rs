@@ -1,20 +1,59 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
 
-// Wallet-based discount tiers (thresholds in lamports)
-const ONE_SOL: u64 = 1_000_000_000u64;
-const TIER_THRESHOLDS: [u64; 3] = [ONE_SOL, 10 * ONE_SOL, 50 * ONE_SOL];
-// Corresponding discounts in basis points (parts per 10_000)
-const TIER_DISCOUNTS_BP: [u128; 4] = [0u128, 1_000u128, 2_500u128, 5_000u128];
+use crate::{
+    errors::TokenMillError,
+    state::{DiscountSchedule, DiscountStrategy, StakePosition, TokenMillConfig, TraderStats},
+};
 
-pub fn compute_discount_bp(wallet_lamports: u64) -> u128 {
-    if wallet_lamports >= TIER_THRESHOLDS[2] {
-        return TIER_DISCOUNTS_BP[3];
-    }
-    if wallet_lamports >= TIER_THRESHOLDS[1] {
-        return TIER_DISCOUNTS_BP[2];
-    }
-    if wallet_lamports >= TIER_THRESHOLDS[0] {
-        return TIER_DISCOUNTS_BP[1];
-    }
-    TIER_DISCOUNTS_BP[0]
+/// Accounts a trader may present to rank in the discount schedule. Only the one matching the
+/// config's `DiscountStrategy` is read.
+#[derive(Default, Clone, Copy)]
+pub struct DiscountAccounts<'a, 'info> {
+    pub discount_schedule: Option<&'a DiscountSchedule>,
+    pub trader_stats: Option<&'a TraderStats>,
+    pub token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub stake_position: Option<&'a StakePosition>,
+}
+
+// Discount in basis points (parts per 10_000) of the highest tier reached by `amount`
+pub fn compute_discount_bp(discount_schedule: Option<&DiscountSchedule>, amount: u64) -> u128 {
+    discount_schedule.map_or(0, |schedule| schedule.discount_bps(amount) as u128)
+}
+
+// Traders without the account required by the strategy, or configs without a schedule, get no
+// discount. Accounts that don't belong to the trader or to the configured mint/market are rejected.
+pub fn compute_trader_discount_bp(
+    config: &TokenMillConfig,
+    trader: &Pubkey,
+    accounts: &DiscountAccounts,
+    timestamp: i64,
+) -> Result<u128> {
+    let amount = match config.discount_strategy {
+        DiscountStrategy::TradingVolume => accounts
+            .trader_stats
+            .map(|stats| stats.rolling_volume(timestamp)),
+        DiscountStrategy::TokenHolding { mint } => match accounts.token_account {
+            Some(token_account) => {
+                if token_account.owner != *trader || token_account.mint != mint {
+                    return Err(error!(TokenMillError::InvalidDiscountAccount));
+                }
+                Some(token_account.amount)
+            }
+            None => None,
+        },
+        DiscountStrategy::StakedBalance { market } => match accounts.stake_position {
+            Some(stake_position) => {
+                if stake_position.user != *trader || stake_position.market != market {
+                    return Err(error!(TokenMillError::InvalidDiscountAccount));
+                }
+                Some(stake_position.amount_staked)
+            }
+            None => None,
+        },
+    };
+
+    Ok(amount.map_or(0, |amount| {
+        compute_discount_bp(accounts.discount_schedule, amount)
+    }))
 }
@@ -33,4 +33,20 @@ pub enum TokenMillError {
     InvalidSwapType,
     InvalidPrice,
     MarketMigrated,
+    DeadlineExceeded,
+    InvalidCurveParameters,
+    InvalidDiscountSchedule,
+    InvalidDiscountAccount,
+    InvalidCreatorFeeSplit,
+    CreatorFeeSplitLocked,
+    InvalidLaunchFee,
+    BuyLimitExceeded,
+    InvalidWalletPosition,
+    MarketNotOpen,
+    InvalidPresaleProof,
+    MarketPaused,
+    InvalidSwapAuthorityScope,
+    SwapAuthorityExpired,
+    SwapDirectionNotAllowed,
+    SwapVolumeCapExceeded,
 }
@@ -2,6 +2,10 @@ use anchor_lang::event;
 use anchor_lang::prelude::*;
 
 use crate::constant::PRICES_LENGTH;
+use crate::state::{DiscountStrategy, FeeRecipient, SwapAuthorityScope};
+use crate::CurveParams;
+use crate::LaunchFeeDecay;
+use crate::LaunchFeeRecipient;
 use crate::QuoteTokenBadgeStatus;
 use crate::SwapType;
 
@@ -38,6 +42,14 @@ pub struct TokenMillMarketCreationEvent {
     pub referral_fee_share: u16,
     pub creator_fee_share: u16,
     pub staking_fee_share: u16,
+    pub curve: CurveParams,
+    /// Buy caps before migration, 0 for no limit
+    pub max_base_per_transaction: u64,
+    pub max_base_per_wallet: u64,
+    /// Opening time of the market, preceded by a presale when `presale_root` is set
+    pub open_at: i64,
+    pub presale_root: Option<[u8; 32]>,
+    pub presale_max_base_per_wallet: u64,
 }
 
 #[event]
@@ -59,6 +71,16 @@ pub struct TokenMillSwapEvent {
     pub staking_fee: u64,
     pub protocol_fee: u64,
     pub referral_fee: u64,
+    /// Anti-sniper fee paid on top of the trade during the launch phase
+    pub launch_fee: u64,
+}
+
+#[event]
+pub struct TokenMillLaunchFeeSetEvent {
+    pub market: Pubkey,
+    pub start_fee_bps: u16,
+    pub decay: LaunchFeeDecay,
+    pub recipient: LaunchFeeRecipient,
 }
 
 #[event]
@@ -76,6 +98,31 @@ pub struct TokenMillCreatorFeeClaimEvent {
     pub fees_distributed: u64,
 }
 
+#[event]
+pub struct TokenMillCreatorFeeDistributionEvent {
+    pub market: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenMillFeeSweepEvent {
+    pub market: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenMillCreatorFeeSplitUpdateEvent {
+    pub market: Pubkey,
+    pub recipients: Vec<FeeRecipient>,
+}
+
+#[event]
+pub struct TokenMillCreatorFeeSplitLockEvent {
+    pub market: Pubkey,
+}
+
 #[event]
 pub struct TokenMillDefaultFeeSharesUpdateEvent {
     pub config: Pubkey,
@@ -83,6 +130,38 @@ pub struct TokenMillDefaultFeeSharesUpdateEvent {
     pub new_referral_fee_share: u16,
 }
 
+#[event]
+pub struct TokenMillDiscountScheduleUpdateEvent {
+    pub config: Pubkey,
+    pub thresholds: Vec<u64>,
+    pub discounts_bps: Vec<u16>,
+}
+
+#[event]
+pub struct TokenMillDiscountStrategyUpdateEvent {
+    pub config: Pubkey,
+    pub new_discount_strategy: DiscountStrategy,
+}
+
+#[event]
+pub struct TokenMillConfigPauseUpdateEvent {
+    pub config: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct TokenMillMarketPauseUpdateEvent {
+    pub market: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct TokenMillFeeSharePolicyUpdateEvent {
+    pub config: Pubkey,
+    pub new_max_creator_fee_share: u16,
+    pub new_fee_share_notice_period: i64,
+}
+
 #[event]
 pub struct TokenMillProtocolFeeRecipientUpdateEvent {
     pub config: Pubkey,
@@ -100,6 +179,7 @@ pub struct TokenMillMarketFeeSharesUpdateEvent {
     pub market: Pubkey,
     pub new_creator_fee_share: u16,
     pub new_staking_fee_share: u16,
+    pub effective_at: i64,
 }
 
 #[event]
@@ -151,6 +231,20 @@ pub struct TokenMillVestingPlanReleaseEvent {
 pub struct TokenMillMarketLockedEvent {
     pub market: Pubkey,
     pub swap_authority: Pubkey,
+    pub scope: SwapAuthorityScope,
+}
+
+#[event]
+pub struct TokenMillSwapAuthorityGrantEvent {
+    pub market: Pubkey,
+    pub swap_authority: Pubkey,
+    pub scope: SwapAuthorityScope,
+}
+
+#[event]
+pub struct TokenMillSwapAuthorityRevokeEvent {
+    pub market: Pubkey,
+    pub swap_authority: Pubkey,
 }
 
 #[event]
@@ -1,6 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::{events::TokenMillConfigCreationEvent, state::TokenMillConfig};
+use crate::{
+    constant::{BPS_DENOMINATOR, DEFAULT_FEE_SHARE_NOTICE_PERIOD},
+    errors::TokenMillError,
+    events::TokenMillConfigCreationEvent,
+    state::{DiscountStrategy, TokenMillConfig},
+};
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -21,6 +26,10 @@ pub fn handler(
     protocol_fee_share: u16,
     referral_fee_share: u16,
 ) -> Result<()> {
+    if protocol_fee_share > BPS_DENOMINATOR || referral_fee_share > BPS_DENOMINATOR {
+        return Err(error!(TokenMillError::InvalidFeeShare));
+    }
+
     // initialize config fields
     let cfg = &mut ctx.accounts.config;
     cfg.authority = authority;
@@ -30,6 +39,10 @@ pub fn handler(
     cfg.referral_fee_share = referral_fee_share;
     cfg.cpi_whitelist = Vec::new();
     cfg.max_forwarded_accounts = 0u8;
+    cfg.max_creator_fee_share = BPS_DENOMINATOR;
+    cfg.fee_share_notice_period = DEFAULT_FEE_SHARE_NOTICE_PERIOD;
+    cfg.discount_strategy = DiscountStrategy::TradingVolume;
+    cfg.paused = false;
 
     emit_cpi!(TokenMillConfigCreationEvent {
         config: ctx.accounts.config.key(),
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillDiscountScheduleUpdateEvent,
+    state::{DiscountSchedule, TokenMillConfig, DISCOUNT_SCHEDULE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateDiscountSchedule<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        init,
+        seeds = [DISCOUNT_SCHEDULE_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump,
+        payer = authority,
+        space = 8 + DiscountSchedule::INIT_SPACE
+    )]
+    pub discount_schedule: Account<'info, DiscountSchedule>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<CreateDiscountSchedule>,
+    thresholds: Vec<u64>,
+    discounts_bps: Vec<u16>,
+) -> Result<()> {
+    let discount_schedule = &mut ctx.accounts.discount_schedule;
+    discount_schedule.config = ctx.accounts.config.key();
+    discount_schedule.bump = ctx.bumps.discount_schedule;
+    discount_schedule.set_tiers(&thresholds, &discounts_bps)?;
+
+    emit_cpi!(TokenMillDiscountScheduleUpdateEvent {
+        config: ctx.accounts.config.key(),
+        thresholds,
+        discounts_bps,
+    });
+
+    Ok(())
+}
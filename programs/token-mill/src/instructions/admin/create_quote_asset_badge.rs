@@ -5,6 +5,7 @@ use crate::{
     errors::TokenMillError,
     events::TokenMillQuoteTokenBadgeEvent,
     state::{QuoteTokenBadge, TokenMillConfig},
+    token_extensions::assert_supported_mint,
     QUOTE_TOKEN_BADGE_PDA_SEED,
 };
 
@@ -36,6 +37,8 @@ pub struct CreateQuoteAssetBadge<'info> {
 }
 
 pub fn handler(ctx: Context<CreateQuoteAssetBadge>) -> Result<()> {
+    assert_supported_mint(&ctx.accounts.token_mint)?;
+
     emit_cpi!(TokenMillQuoteTokenBadgeEvent {
         config: ctx.accounts.config.key(),
         quote_token_mint: ctx.accounts.token_mint.key(),
@@ -1,15 +1,24 @@
 pub mod accept_config_ownership;
 pub mod create_config;
+pub mod create_discount_schedule;
 pub mod create_quote_asset_badge;
 pub mod transfer_config_ownership;
 pub mod update_default_fee_shares;
+pub mod update_discount_schedule;
+pub mod update_discount_strategy;
+pub mod update_fee_share_policy;
 pub mod update_protocol_fee_recipient;
 pub mod update_quote_asset_badge;
 pub mod update_cpi_whitelist;
+pub mod update_config_pause;
+pub mod update_market_pause;
 
 pub use accept_config_ownership::*;
 pub use create_config::*;
+pub use create_discount_schedule::*;
 pub use create_quote_asset_badge::*;
 pub use transfer_config_ownership::*;
+pub use update_discount_schedule::*;
 pub use update_quote_asset_badge::*;
 pub use update_cpi_whitelist::*;
+pub use update_market_pause::*;
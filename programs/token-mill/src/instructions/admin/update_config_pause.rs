@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::events::TokenMillConfigPauseUpdateEvent;
+
+// Pauses trading, buybacks, staking deposits and reward claims on every market of the config,
+// withdrawals and fee claims stay open
+pub fn handler(ctx: Context<ConfigUpdate>, paused: bool) -> Result<()> {
+    ctx.accounts.config.paused = paused;
+
+    emit_cpi!(TokenMillConfigPauseUpdateEvent {
+        config: ctx.accounts.config.key(),
+        paused,
+    });
+
+    Ok(())
+}
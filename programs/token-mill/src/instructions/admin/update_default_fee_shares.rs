@@ -1,13 +1,28 @@
 use anchor_lang::prelude::*;
 
 use super::ConfigUpdate;
-use crate::events::TokenMillDefaultFeeSharesUpdateEvent;
+use crate::{
+    constant::BPS_DENOMINATOR, errors::TokenMillError, events::TokenMillDefaultFeeSharesUpdateEvent,
+};
 
+// Existing markets keep their creator and staking shares, the protocol share applies to new
+// markets and to the markets updating their shares. Swaps and lamport trades both charge the
+// protocol the remainder of the market's own shares, so live markets only see the new share
+// once their next share update takes effect after the notice period
 pub fn handler(
     ctx: Context<ConfigUpdate>,
     new_default_protocol_fee_share: u16,
     new_referral_fee_share: u16,
 ) -> Result<()> {
+    if new_default_protocol_fee_share > BPS_DENOMINATOR || new_referral_fee_share > BPS_DENOMINATOR
+    {
+        return Err(error!(TokenMillError::InvalidFeeShare));
+    }
+
+    let config = &mut ctx.accounts.config;
+    config.default_protocol_fee_share = new_default_protocol_fee_share;
+    config.referral_fee_share = new_referral_fee_share;
+
     emit_cpi!(TokenMillDefaultFeeSharesUpdateEvent {
         config: ctx.accounts.config.key(),
         new_default_protocol_fee_share,
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillDiscountScheduleUpdateEvent,
+    state::{DiscountSchedule, TokenMillConfig, DISCOUNT_SCHEDULE_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateDiscountSchedule<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        seeds = [DISCOUNT_SCHEDULE_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump = discount_schedule.bump,
+    )]
+    pub discount_schedule: Account<'info, DiscountSchedule>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateDiscountSchedule>,
+    thresholds: Vec<u64>,
+    discounts_bps: Vec<u16>,
+) -> Result<()> {
+    ctx.accounts
+        .discount_schedule
+        .set_tiers(&thresholds, &discounts_bps)?;
+
+    emit_cpi!(TokenMillDiscountScheduleUpdateEvent {
+        config: ctx.accounts.config.key(),
+        thresholds,
+        discounts_bps,
+    });
+
+    Ok(())
+}
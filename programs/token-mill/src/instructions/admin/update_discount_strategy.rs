@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{events::TokenMillDiscountStrategyUpdateEvent, state::DiscountStrategy};
+
+pub fn handler(ctx: Context<ConfigUpdate>, new_discount_strategy: DiscountStrategy) -> Result<()> {
+    ctx.accounts.config.discount_strategy = new_discount_strategy;
+
+    emit_cpi!(TokenMillDiscountStrategyUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_discount_strategy,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use super::ConfigUpdate;
+use crate::{
+    constant::BPS_DENOMINATOR, errors::TokenMillError, events::TokenMillFeeSharePolicyUpdateEvent,
+};
+
+pub fn handler(
+    ctx: Context<ConfigUpdate>,
+    new_max_creator_fee_share: u16,
+    new_fee_share_notice_period: i64,
+) -> Result<()> {
+    if new_max_creator_fee_share > BPS_DENOMINATOR {
+        return Err(error!(TokenMillError::InvalidFeeShare));
+    }
+    if new_fee_share_notice_period < 0 {
+        return Err(error!(TokenMillError::InvalidConfig));
+    }
+
+    let config = &mut ctx.accounts.config;
+    config.max_creator_fee_share = new_max_creator_fee_share;
+    config.fee_share_notice_period = new_fee_share_notice_period;
+
+    emit_cpi!(TokenMillFeeSharePolicyUpdateEvent {
+        config: ctx.accounts.config.key(),
+        new_max_creator_fee_share,
+        new_fee_share_notice_period,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillMarketPauseUpdateEvent,
+    state::{Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MarketPauseUpdate<'info> {
+    #[account(has_one = authority @ TokenMillError::InvalidAuthority)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<MarketPauseUpdate>, paused: bool) -> Result<()> {
+    ctx.accounts.market.load_mut()?.is_paused = paused as u8;
+
+    emit_cpi!(TokenMillMarketPauseUpdateEvent {
+        market: ctx.accounts.market.key(),
+        paused,
+    });
+
+    Ok(())
+}
@@ -1,16 +1,25 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
+    token::spl_token::native_mint,
     token_2022::Token2022,
-    token_interface::{Mint, TokenAccount},
+    token_interface::{
+        self, spl_token_metadata_interface::state::TokenMetadata, Mint, MintTo, TokenAccount,
+        TokenMetadataInitialize,
+    },
 };
 
 use crate::{
-    constant::MILL_TOKEN_DECIMALS,
+    constant::{MILL_TOKEN_DECIMALS, PRICES_LENGTH},
     errors::TokenMillError,
     events::TokenMillMarketCreationEvent,
-    state::{Market, TokenMillConfig},
-    QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED, QUOTE_TOKEN_BADGE_PDA_SEED,
+    state::{Market, TokenMillConfig, MARKET_LAYOUT_VERSION},
+    token_extensions::assert_supported_mint,
+    CurveParams, QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED,
+    QUOTE_TOKEN_BADGE_PDA_SEED,
 };
 
 #[event_cpi]
@@ -70,13 +79,103 @@ pub struct CreateMarket<'info> {
 
 pub fn handler(
     ctx: Context<CreateMarket>,
-    _name: String,
-    _symbol: String,
-    _uri: String,
+    name: String,
+    symbol: String,
+    uri: String,
     total_supply: u64,
     creator_fee_share: u16,
     staking_fee_share: u16,
+    curve: CurveParams,
+    max_base_per_transaction: u64,
+    max_base_per_wallet: u64,
+    open_at: i64,
+    presale_root: Option<[u8; 32]>,
+    presale_max_base_per_wallet: u64,
 ) -> Result<()> {
+    let params = MarketParameters {
+        total_supply,
+        creator_fee_share,
+        staking_fee_share,
+        curve,
+        max_base_per_transaction,
+        max_base_per_wallet,
+        open_at,
+        presale_root,
+        presale_max_base_per_wallet,
+    };
+    let bump = ctx.bumps.market;
+    let has_reserve = initialize_market(
+        &ctx.accounts.market,
+        &ctx.accounts.config,
+        ctx.accounts.creator.key(),
+        ctx.accounts.base_token_mint.key(),
+        &ctx.accounts.quote_token_mint,
+        bump,
+        &params,
+    )?;
+
+    // The mint was created with room for the metadata pointer only, the metadata itself is
+    // appended by the token program and must be funded beforehand
+    let metadata = TokenMetadata {
+        name: name.clone(),
+        symbol: symbol.clone(),
+        uri: uri.clone(),
+        ..Default::default()
+    };
+    let base_token_mint_info = ctx.accounts.base_token_mint.to_account_info();
+    let metadata_len = metadata.tlv_size_of()?;
+    let required_lamports =
+        Rent::get()?.minimum_balance(base_token_mint_info.data_len() + metadata_len);
+    let missing_lamports = required_lamports.saturating_sub(base_token_mint_info.lamports());
+    if missing_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: base_token_mint_info.clone(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+
+    let base_token_mint_key = ctx.accounts.base_token_mint.key();
+    let seeds: &[&[u8]] = &[
+        MARKET_PDA_SEED.as_bytes(),
+        base_token_mint_key.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    token_interface::token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                program_id: ctx.accounts.token_program.to_account_info(),
+                metadata: base_token_mint_info.clone(),
+                update_authority: ctx.accounts.market.to_account_info(),
+                mint_authority: ctx.accounts.market.to_account_info(),
+                mint: base_token_mint_info,
+            },
+            signer_seeds,
+        ),
+        name,
+        symbol,
+        uri,
+    )?;
+
+    if has_reserve {
+        fund_base_reserve(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.base_token_mint.to_account_info(),
+            ctx.accounts.market_base_token_ata.to_account_info(),
+            ctx.accounts.market.to_account_info(),
+            signer_seeds,
+            total_supply,
+        )?;
+    }
+
     emit_cpi!(TokenMillMarketCreationEvent {
         config: ctx.accounts.config.key(),
         market: ctx.accounts.market.key(),
@@ -88,7 +187,114 @@ pub fn handler(
         referral_fee_share: ctx.accounts.config.referral_fee_share,
         creator_fee_share,
         staking_fee_share,
+        curve,
+        max_base_per_transaction,
+        max_base_per_wallet,
+        open_at,
+        presale_root,
+        presale_max_base_per_wallet,
     });
 
     Ok(())
 }
+
+/// Parameters of a new market, shared by every market creation instruction.
+pub struct MarketParameters {
+    pub total_supply: u64,
+    pub creator_fee_share: u16,
+    pub staking_fee_share: u16,
+    pub curve: CurveParams,
+    pub max_base_per_transaction: u64,
+    pub max_base_per_wallet: u64,
+    pub open_at: i64,
+    pub presale_root: Option<[u8; 32]>,
+    pub presale_max_base_per_wallet: u64,
+}
+
+/// Validates `params` and writes the initial state of a new market, shared by every market
+/// creation instruction. Returns whether the market trades against a base reserve, which must
+/// then be funded with `fund_base_reserve`.
+///
+/// SPL-quoted markets trade through `swap` against a reserve holding the whole supply, while
+/// lamport markets mint and burn on `buy` and `sell`.
+pub fn initialize_market(
+    market: &AccountLoader<Market>,
+    config: &Account<TokenMillConfig>,
+    creator: Pubkey,
+    base_token_mint: Pubkey,
+    quote_token_mint: &InterfaceAccount<Mint>,
+    bump: u8,
+    params: &MarketParameters,
+) -> Result<bool> {
+    validate_market_parameters(
+        config,
+        params.total_supply,
+        params.creator_fee_share,
+        params.staking_fee_share,
+    )?;
+    assert_supported_mint(quote_token_mint)?;
+
+    let has_reserve = quote_token_mint.key() != native_mint::ID;
+
+    let mut market = market.load_init()?;
+
+    market.config = config.key();
+    market.creator = creator;
+    market.base_token_mint = base_token_mint;
+    market.quote_token_mint = quote_token_mint.key();
+    market.quote_token_decimals = quote_token_mint.decimals;
+    market.bump = bump;
+    market.layout_version = MARKET_LAYOUT_VERSION;
+    market.total_supply = params.total_supply;
+    market.base_reserve = if has_reserve { params.total_supply } else { 0 };
+    market.fees.creator_fee_share = params.creator_fee_share;
+    market.fees.staking_fee_share = params.staking_fee_share;
+    market.buy_limits.max_base_per_transaction = params.max_base_per_transaction;
+    market.buy_limits.max_base_per_wallet = params.max_base_per_wallet;
+    market.launch_window.open_at = params.open_at;
+    market.launch_window.presale_root = params.presale_root.unwrap_or_default();
+    market.launch_window.presale_max_base_per_wallet = params.presale_max_base_per_wallet;
+
+    market.set_curve(params.curve, params.total_supply, quote_token_mint.decimals)?;
+
+    Ok(has_reserve)
+}
+
+/// Mints the whole supply of a market into its base reserve, with the market PDA signing as
+/// mint authority.
+pub fn fund_base_reserve<'info>(
+    token_program: AccountInfo<'info>,
+    base_token_mint: AccountInfo<'info>,
+    market_base_token_ata: AccountInfo<'info>,
+    market: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    total_supply: u64,
+) -> Result<()> {
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            token_program,
+            MintTo {
+                mint: base_token_mint,
+                to: market_base_token_ata,
+                authority: market,
+            },
+            signer_seeds,
+        ),
+        total_supply,
+    )
+}
+
+/// Checks shared by every market creation instruction.
+pub fn validate_market_parameters(
+    config: &TokenMillConfig,
+    total_supply: u64,
+    creator_fee_share: u16,
+    staking_fee_share: u16,
+) -> Result<()> {
+    // The price-array curve splits the supply into `PRICES_LENGTH - 1` equal segments
+    if total_supply == 0 || total_supply % (PRICES_LENGTH - 1) as u64 != 0 {
+        return Err(error!(TokenMillError::InvalidTotalSupply));
+    }
+
+    config.validate_market_fee_shares(creator_fee_share, staking_fee_share)
+}
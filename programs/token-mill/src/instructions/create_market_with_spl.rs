@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    metadata::Metadata,
+    metadata::{self, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3, Metadata},
     token::{Mint, Token, TokenAccount},
     token_interface::Mint as MintInterface,
 };
@@ -10,8 +10,11 @@ use crate::{
     constant::MILL_TOKEN_DECIMALS,
     errors::TokenMillError,
     events::TokenMillMarketCreationEvent,
+    instructions::create_market::{fund_base_reserve, initialize_market, MarketParameters},
     state::{Market, TokenMillConfig},
-    QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED, QUOTE_TOKEN_BADGE_PDA_SEED,
+    token_extensions::assert_supported_mint_info,
+    CurveParams, QuoteTokenBadge, QuoteTokenBadgeStatus, MARKET_PDA_SEED,
+    QUOTE_TOKEN_BADGE_PDA_SEED,
 };
 
 #[event_cpi]
@@ -37,7 +40,16 @@ pub struct CreateMarketWithSpl<'info> {
     pub base_token_mint: Box<Account<'info, Mint>>,
 
     /// CHECK: New Metaplex Account being created
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            token_metadata_program.key().as_ref(),
+            base_token_mint.key().as_ref(),
+        ],
+        bump,
+        seeds::program = token_metadata_program.key()
+    )]
     pub base_token_metadata: UncheckedAccount<'info>,
 
     #[account(
@@ -69,17 +81,96 @@ pub struct CreateMarketWithSpl<'info> {
     pub token_program: Program<'info, Token>,
     pub token_metadata_program: Program<'info, Metadata>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
 }
 
 pub fn handler(
     ctx: Context<CreateMarketWithSpl>,
-    _name: String,
-    _symbol: String,
-    _uri: String,
+    name: String,
+    symbol: String,
+    uri: String,
     total_supply: u64,
     creator_fee_share: u16,
     staking_fee_share: u16,
+    curve: CurveParams,
+    max_base_per_transaction: u64,
+    max_base_per_wallet: u64,
+    open_at: i64,
+    presale_root: Option<[u8; 32]>,
+    presale_max_base_per_wallet: u64,
 ) -> Result<()> {
+    let params = MarketParameters {
+        total_supply,
+        creator_fee_share,
+        staking_fee_share,
+        curve,
+        max_base_per_transaction,
+        max_base_per_wallet,
+        open_at,
+        presale_root,
+        presale_max_base_per_wallet,
+    };
+    assert_supported_mint_info(&ctx.accounts.base_token_mint.to_account_info())?;
+
+    let bump = ctx.bumps.market;
+    let has_reserve = initialize_market(
+        &ctx.accounts.market,
+        &ctx.accounts.config,
+        ctx.accounts.creator.key(),
+        ctx.accounts.base_token_mint.key(),
+        &ctx.accounts.quote_token_mint,
+        bump,
+        &params,
+    )?;
+
+    let base_token_mint_key = ctx.accounts.base_token_mint.key();
+    let seeds: &[&[u8]] = &[
+        MARKET_PDA_SEED.as_bytes(),
+        base_token_mint_key.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    // The market PDA is both the mint authority and the update authority of the metadata
+    metadata::create_metadata_accounts_v3(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_metadata_program.to_account_info(),
+            CreateMetadataAccountsV3 {
+                metadata: ctx.accounts.base_token_metadata.to_account_info(),
+                mint: ctx.accounts.base_token_mint.to_account_info(),
+                mint_authority: ctx.accounts.market.to_account_info(),
+                payer: ctx.accounts.creator.to_account_info(),
+                update_authority: ctx.accounts.market.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                rent: ctx.accounts.rent.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        DataV2 {
+            name,
+            symbol,
+            uri,
+            seller_fee_basis_points: 0,
+            creators: None,
+            collection: None,
+            uses: None,
+        },
+        true,
+        true,
+        None,
+    )?;
+
+    if has_reserve {
+        fund_base_reserve(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.base_token_mint.to_account_info(),
+            ctx.accounts.market_base_token_ata.to_account_info(),
+            ctx.accounts.market.to_account_info(),
+            signer_seeds,
+            total_supply,
+        )?;
+    }
+
     emit_cpi!(TokenMillMarketCreationEvent {
         config: ctx.accounts.config.key(),
         market: ctx.accounts.market.key(),
@@ -91,6 +182,12 @@ pub fn handler(
         referral_fee_share: ctx.accounts.config.referral_fee_share,
         creator_fee_share,
         staking_fee_share,
+        curve,
+        max_base_per_transaction,
+        max_base_per_wallet,
+        open_at,
+        presale_root,
+        presale_max_base_per_wallet,
     });
 
     Ok(())
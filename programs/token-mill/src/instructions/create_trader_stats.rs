@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{TokenMillConfig, TraderStats, TRADER_STATS_PDA_SEED};
+
+#[derive(Accounts)]
+pub struct CreateTraderStats<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        init,
+        seeds = [TRADER_STATS_PDA_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + TraderStats::INIT_SPACE
+    )]
+    pub trader_stats: Account<'info, TraderStats>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateTraderStats>) -> Result<()> {
+    let trader_stats = &mut ctx.accounts.trader_stats;
+    trader_stats.config = ctx.accounts.config.key();
+    trader_stats.user = ctx.accounts.user.key();
+    trader_stats.bump = ctx.bumps.trader_stats;
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{Market, WalletPosition, WALLET_POSITION_PDA_SEED};
+
+#[derive(Accounts)]
+pub struct CreateWalletPosition<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        seeds = [WALLET_POSITION_PDA_SEED.as_bytes(), market.key().as_ref(), user.key().as_ref()],
+        bump,
+        payer = user,
+        space = 8 + WalletPosition::INIT_SPACE
+    )]
+    pub wallet_position: Account<'info, WalletPosition>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateWalletPosition>) -> Result<()> {
+    let wallet_position = &mut ctx.accounts.wallet_position;
+    wallet_position.market = ctx.accounts.market.key();
+    wallet_position.user = ctx.accounts.user.key();
+    wallet_position.bump = ctx.bumps.wallet_position;
+
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
-use crate::{errors::TokenMillError, events::TokenMillCreatorFeeClaimEvent, state::Market};
+use crate::{
+    errors::TokenMillError,
+    events::{TokenMillCreatorFeeClaimEvent, TokenMillCreatorFeeDistributionEvent},
+    state::{CreatorFeeSplit, Market, CREATOR_FEE_SPLIT_PDA_SEED, MARKET_PDA_SEED},
+};
 
 #[event_cpi]
 #[derive(Accounts)]
@@ -33,11 +37,97 @@ pub struct ClaimCreatorFees<'info> {
 
     pub creator: Signer<'info>,
 
+    /// Required once the market has a fee split. The quote token accounts of its recipients are
+    /// then passed as remaining accounts, in the order of the split.
+    #[account(
+        seeds = [CREATOR_FEE_SPLIT_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = creator_fee_split.bump,
+    )]
+    pub creator_fee_split: Option<Account<'info, CreatorFeeSplit>>,
+
     pub quote_token_program: Interface<'info, TokenInterface>,
 }
 
-pub fn handler(ctx: Context<ClaimCreatorFees>) -> Result<()> {
-    let pending_fees = 0;
+/// Transfers the creator fees accrued by swaps out of the market's quote token account, either
+/// to the creator or pro-rata to the recipients of the market's fee split.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ClaimCreatorFees<'info>>) -> Result<()> {
+    let (pending_fees, base_token_mint, bump, has_creator_fee_split) = {
+        let mut market = ctx.accounts.market.load_mut()?;
+        let pending_fees = market.fees.pending_creator_fees;
+        market.fees.pending_creator_fees = 0;
+
+        (
+            pending_fees,
+            market.base_token_mint,
+            market.bump,
+            market.has_creator_fee_split != 0,
+        )
+    };
+
+    let seeds: &[&[u8]] = &[
+        MARKET_PDA_SEED.as_bytes(),
+        base_token_mint.as_ref(),
+        &[bump],
+    ];
+    let transfer_fees = |to: AccountInfo<'info>, amount: u64| -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.quote_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.market_quote_token_ata.to_account_info(),
+                    mint: ctx.accounts.quote_token_mint.to_account_info(),
+                    to,
+                    authority: ctx.accounts.market.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            ctx.accounts.quote_token_mint.decimals,
+        )
+    };
+
+    if has_creator_fee_split {
+        let creator_fee_split = ctx
+            .accounts
+            .creator_fee_split
+            .as_ref()
+            .ok_or(error!(TokenMillError::InvalidCreatorFeeSplit))?;
+        if ctx.remaining_accounts.len() != creator_fee_split.recipients.len() {
+            return Err(error!(TokenMillError::InvalidCreatorFeeSplit));
+        }
+
+        let shares = creator_fee_split.split(pending_fees);
+        for ((recipient, share), account_info) in creator_fee_split
+            .recipients
+            .iter()
+            .zip(shares)
+            .zip(ctx.remaining_accounts)
+        {
+            let token_account = InterfaceAccount::<TokenAccount>::try_from(account_info)?;
+            if token_account.owner != recipient.recipient
+                || token_account.mint != ctx.accounts.quote_token_mint.key()
+            {
+                return Err(error!(TokenMillError::InvalidCreatorFeeSplit));
+            }
+
+            transfer_fees(account_info.clone(), share)?;
+
+            emit_cpi!(TokenMillCreatorFeeDistributionEvent {
+                market: ctx.accounts.market.key(),
+                recipient: recipient.recipient,
+                amount: share,
+            });
+        }
+    } else {
+        transfer_fees(
+            ctx.accounts.creator_quote_token_ata.to_account_info(),
+            pending_fees,
+        )?;
+    }
 
     emit_cpi!(TokenMillCreatorFeeClaimEvent {
         market: ctx.accounts.market.key(),
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillCreatorFeeSplitUpdateEvent,
+    state::{CreatorFeeSplit, FeeRecipient, Market, CREATOR_FEE_SPLIT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateCreatorFeeSplit<'info> {
+    #[account(mut, has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        seeds = [CREATOR_FEE_SPLIT_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + CreatorFeeSplit::INIT_SPACE
+    )]
+    pub creator_fee_split: Account<'info, CreatorFeeSplit>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreateCreatorFeeSplit>, recipients: Vec<FeeRecipient>) -> Result<()> {
+    let creator_fee_split = &mut ctx.accounts.creator_fee_split;
+    creator_fee_split.market = ctx.accounts.market.key();
+    creator_fee_split.bump = ctx.bumps.creator_fee_split;
+    creator_fee_split.is_locked = false;
+    creator_fee_split.set_recipients(recipients.clone())?;
+
+    // From now on creator fees are only paid out through the split
+    ctx.accounts.market.load_mut()?.has_creator_fee_split = 1;
+
+    emit_cpi!(TokenMillCreatorFeeSplitUpdateEvent {
+        market: ctx.accounts.market.key(),
+        recipients,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use super::CreatorFeeSplitUpdate;
+use crate::{errors::TokenMillError, events::TokenMillCreatorFeeSplitLockEvent};
+
+pub fn handler(ctx: Context<CreatorFeeSplitUpdate>) -> Result<()> {
+    let creator_fee_split = &mut ctx.accounts.creator_fee_split;
+    if creator_fee_split.is_locked {
+        return Err(error!(TokenMillError::CreatorFeeSplitLocked));
+    }
+    creator_fee_split.is_locked = true;
+
+    emit_cpi!(TokenMillCreatorFeeSplitLockEvent {
+        market: ctx.accounts.market.key(),
+    });
+
+    Ok(())
+}
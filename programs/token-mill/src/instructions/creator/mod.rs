@@ -1,7 +1,14 @@
 pub mod claim_creator_fees;
+pub mod create_creator_fee_split;
+pub mod lock_creator_fee_split;
+pub mod set_launch_fee;
 pub mod set_market_prices;
 pub mod update_creator;
+pub mod update_creator_fee_split;
 pub mod update_market_fee_shares;
 
 pub use claim_creator_fees::*;
+pub use create_creator_fee_split::*;
 pub use set_market_prices::*;
+pub use update_creator_fee_split::*;
+pub use update_market_fee_shares::*;
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use super::MarketSettingsUpdate;
+use crate::{
+    constant::MAX_LAUNCH_FEE_BPS,
+    errors::TokenMillError,
+    events::TokenMillLaunchFeeSetEvent,
+    state::LaunchFee,
+    LaunchFeeDecay, LaunchFeeRecipient,
+};
+
+// The launch fee can only be set before the first trade, as its decay starts with it
+pub fn handler(
+    ctx: Context<MarketSettingsUpdate>,
+    start_fee_bps: u16,
+    decay: LaunchFeeDecay,
+    recipient: LaunchFeeRecipient,
+) -> Result<()> {
+    let duration = match decay {
+        LaunchFeeDecay::Slots(slots) => slots,
+        LaunchFeeDecay::Seconds(seconds) => seconds,
+    };
+    if start_fee_bps > MAX_LAUNCH_FEE_BPS || (start_fee_bps != 0 && duration == 0) {
+        return Err(error!(TokenMillError::InvalidLaunchFee));
+    }
+
+    {
+        let mut market = ctx.accounts.market.load_mut()?;
+
+        if market.launch_fee.started_at != 0 || market.circulating_supply != 0 {
+            return Err(error!(TokenMillError::InvalidMarketState));
+        }
+
+        market.launch_fee = LaunchFee::new(start_fee_bps, decay, recipient);
+    }
+
+    emit_cpi!(TokenMillLaunchFeeSetEvent {
+        market: ctx.accounts.market.key(),
+        start_fee_bps,
+        decay,
+        recipient,
+    });
+
+    Ok(())
+}
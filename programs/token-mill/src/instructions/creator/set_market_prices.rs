@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    constant::PRICES_LENGTH, errors::TokenMillError, events::TokenMillMarketPriceSetEvent,
-    state::Market,
+    constant::{MAX_PRICE, PRICES_LENGTH},
+    errors::TokenMillError,
+    events::TokenMillMarketPriceSetEvent,
+    state::{CurveKind, Market},
 };
 
 #[event_cpi]
@@ -14,11 +16,48 @@ pub struct MarketSettingsUpdate<'info> {
     pub creator: Signer<'info>,
 }
 
+// Prices can only be set once, as changing them would break the reserve backing the bid curve
 pub fn handler(
     ctx: Context<MarketSettingsUpdate>,
     bid_prices: [u64; PRICES_LENGTH],
     ask_prices: [u64; PRICES_LENGTH],
 ) -> Result<()> {
+    {
+        let mut market = ctx.accounts.market.load_mut()?;
+
+        if market.curve_kind()? != CurveKind::PriceArray {
+            return Err(error!(TokenMillError::InvalidMarketState));
+        }
+        if market.width_scaled != 0 {
+            return Err(error!(TokenMillError::PricesAlreadySet));
+        }
+
+        let max_price = (MAX_PRICE as u128)
+            .checked_mul(10u128.pow(market.quote_token_decimals as u32))
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+
+        for i in 0..PRICES_LENGTH {
+            if bid_prices[i] > ask_prices[i] {
+                return Err(error!(TokenMillError::BidAskMismatch));
+            }
+            if i > 0 && (bid_prices[i] < bid_prices[i - 1] || ask_prices[i] < ask_prices[i - 1]) {
+                return Err(error!(TokenMillError::DecreasingPrices));
+            }
+        }
+        if ask_prices[PRICES_LENGTH - 1] as u128 > max_price {
+            return Err(error!(TokenMillError::PriceTooHigh));
+        }
+
+        let width_scaled = market.total_supply / (PRICES_LENGTH - 1) as u64;
+        if width_scaled == 0 {
+            return Err(error!(TokenMillError::InvalidTotalSupply));
+        }
+
+        market.bid_prices = bid_prices;
+        market.ask_prices = ask_prices;
+        market.width_scaled = width_scaled;
+    }
+
     emit_cpi!(TokenMillMarketPriceSetEvent {
         market: ctx.accounts.market.key(),
         bid_prices,
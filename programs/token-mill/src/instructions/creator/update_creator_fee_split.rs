@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillCreatorFeeSplitUpdateEvent,
+    state::{CreatorFeeSplit, FeeRecipient, Market, CREATOR_FEE_SPLIT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreatorFeeSplitUpdate<'info> {
+    #[account(has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_FEE_SPLIT_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = creator_fee_split.bump,
+    )]
+    pub creator_fee_split: Account<'info, CreatorFeeSplit>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<CreatorFeeSplitUpdate>, recipients: Vec<FeeRecipient>) -> Result<()> {
+    let creator_fee_split = &mut ctx.accounts.creator_fee_split;
+    if creator_fee_split.is_locked {
+        return Err(error!(TokenMillError::CreatorFeeSplitLocked));
+    }
+    creator_fee_split.set_recipients(recipients.clone())?;
+
+    emit_cpi!(TokenMillCreatorFeeSplitUpdateEvent {
+        market: ctx.accounts.market.key(),
+        recipients,
+    });
+
+    Ok(())
+}
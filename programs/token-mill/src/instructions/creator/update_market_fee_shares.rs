@@ -1,17 +1,60 @@
 use anchor_lang::prelude::*;
 
-use super::MarketSettingsUpdate;
-use crate::events::TokenMillMarketFeeSharesUpdateEvent;
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillMarketFeeSharesUpdateEvent,
+    state::{Market, TokenMillConfig},
+};
 
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MarketFeeSharesUpdate<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = creator @ TokenMillError::InvalidAuthority
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub creator: Signer<'info>,
+}
+
+// Once tokens are circulating, new shares only take effect after the config notice period
 pub fn handler(
-    ctx: Context<MarketSettingsUpdate>,
+    ctx: Context<MarketFeeSharesUpdate>,
     new_creator_fee_share: u16,
     new_staking_fee_share: u16,
 ) -> Result<()> {
+    let config = &ctx.accounts.config;
+    config.validate_market_fee_shares(new_creator_fee_share, new_staking_fee_share)?;
+
+    let effective_at = {
+        let mut market = ctx.accounts.market.load_mut()?;
+
+        let now = Clock::get()?.unix_timestamp;
+        let effective_at = if market.circulating_supply == 0 {
+            now
+        } else {
+            now.checked_add(config.fee_share_notice_period)
+                .ok_or(error!(TokenMillError::MathOverflow))?
+        };
+        market.schedule_fee_shares(
+            new_creator_fee_share,
+            new_staking_fee_share,
+            now,
+            effective_at,
+        );
+
+        effective_at
+    };
+
     emit_cpi!(TokenMillMarketFeeSharesUpdateEvent {
         market: ctx.accounts.market.key(),
         new_creator_fee_share,
         new_staking_fee_share,
+        effective_at,
     });
 
     Ok(())
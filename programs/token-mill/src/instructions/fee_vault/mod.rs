@@ -0,0 +1,7 @@
+pub mod sweep_creator_fees;
+pub mod sweep_protocol_fees;
+pub mod sweep_referral_fees;
+
+pub use sweep_creator_fees::*;
+pub use sweep_protocol_fees::*;
+pub use sweep_referral_fees::*;
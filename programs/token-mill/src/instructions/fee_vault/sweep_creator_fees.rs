@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillCreatorFeeDistributionEvent,
+    lamports::pay_from_market,
+    state::{CreatorFeeSplit, Market, CREATOR_FEE_SPLIT_PDA_SEED},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepCreatorFees<'info> {
+    #[account(mut, has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    /// CHECK: receives the creator fees in lamports when the market has no fee split, checked
+    /// against `market.creator`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Required once the market has a fee split. Its recipients are then passed as remaining
+    /// accounts, in the order of the split, and receive the fees instead of `creator`.
+    #[account(
+        seeds = [CREATOR_FEE_SPLIT_PDA_SEED.as_bytes(), market.key().as_ref()],
+        bump = creator_fee_split.bump,
+    )]
+    pub creator_fee_split: Option<Account<'info, CreatorFeeSplit>>,
+}
+
+/// Pays the creator fees accrued by lamport trades to the creator, or pro-rata to the recipients
+/// of the market's fee split. Anyone can sweep them.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, SweepCreatorFees<'info>>) -> Result<()> {
+    let (amount, has_creator_fee_split) = {
+        let mut market = ctx.accounts.market.load_mut()?;
+        let amount = market.fee_vault.pending_creator_lamports;
+        market.fee_vault.pending_creator_lamports = 0;
+        (amount, market.has_creator_fee_split != 0)
+    };
+    let market_info = ctx.accounts.market.to_account_info();
+
+    if !has_creator_fee_split {
+        pay_from_market(
+            &market_info,
+            &ctx.accounts.creator.to_account_info(),
+            amount,
+        )?;
+
+        emit_cpi!(TokenMillCreatorFeeDistributionEvent {
+            market: ctx.accounts.market.key(),
+            recipient: ctx.accounts.creator.key(),
+            amount,
+        });
+
+        return Ok(());
+    }
+
+    let creator_fee_split = ctx
+        .accounts
+        .creator_fee_split
+        .as_ref()
+        .ok_or(error!(TokenMillError::InvalidCreatorFeeSplit))?;
+    if ctx.remaining_accounts.len() != creator_fee_split.recipients.len() {
+        return Err(error!(TokenMillError::InvalidCreatorFeeSplit));
+    }
+
+    for ((recipient, share), account_info) in creator_fee_split
+        .recipients
+        .iter()
+        .zip(creator_fee_split.split(amount))
+        .zip(ctx.remaining_accounts)
+    {
+        if account_info.key() != recipient.recipient {
+            return Err(error!(TokenMillError::InvalidCreatorFeeSplit));
+        }
+        pay_from_market(&market_info, account_info, share)?;
+
+        emit_cpi!(TokenMillCreatorFeeDistributionEvent {
+            market: ctx.accounts.market.key(),
+            recipient: recipient.recipient,
+            amount: share,
+        });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillFeeSweepEvent,
+    lamports::pay_from_market,
+    state::{Market, TokenMillConfig},
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepProtocolFees<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub market: AccountLoader<'info, Market>,
+
+    /// CHECK: receives the protocol fees in lamports, checked against the config
+    #[account(
+        mut,
+        address = config.protocol_fee_recipient @ TokenMillError::InvalidAuthority
+    )]
+    pub protocol_fee_recipient: UncheckedAccount<'info>,
+}
+
+/// Pays the protocol fees accrued by lamport trades to the protocol fee recipient. Anyone can
+/// sweep them.
+pub fn handler(ctx: Context<SweepProtocolFees>) -> Result<()> {
+    let amount = {
+        let mut market = ctx.accounts.market.load_mut()?;
+        let amount = market.fee_vault.pending_protocol_lamports;
+        market.fee_vault.pending_protocol_lamports = 0;
+        amount
+    };
+
+    pay_from_market(
+        &ctx.accounts.market.to_account_info(),
+        &ctx.accounts.protocol_fee_recipient.to_account_info(),
+        amount,
+    )?;
+
+    emit_cpi!(TokenMillFeeSweepEvent {
+        market: ctx.accounts.market.key(),
+        recipient: ctx.accounts.protocol_fee_recipient.key(),
+        amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError, events::TokenMillFeeSweepEvent, lamports::pay_from_market,
+    state::Market, ReferralAccount,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepReferralFees<'info> {
+    #[account(mut)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut)]
+    pub referral_account: Account<'info, ReferralAccount>,
+}
+
+/// Moves lamports from the market's referral pool to a referral account, until its balance
+/// backs the `pending_lamports` credited to it. Anyone can sweep them.
+///
+/// Pools of markets sharing a config hold the referral fees of all their referral accounts, so
+/// any of them can back a referral account of that config.
+pub fn handler(ctx: Context<SweepReferralFees>) -> Result<()> {
+    let referral_info = ctx.accounts.referral_account.to_account_info();
+    let min_balance = Rent::get()?.minimum_balance(referral_info.data_len());
+    let backed_lamports = referral_info.lamports().saturating_sub(min_balance);
+
+    let amount = {
+        let mut market = ctx.accounts.market.load_mut()?;
+        if ctx.accounts.referral_account.config != market.config {
+            return Err(error!(TokenMillError::InvalidReferralAccount));
+        }
+
+        let amount = ctx
+            .accounts
+            .referral_account
+            .pending_lamports
+            .saturating_sub(backed_lamports)
+            .min(market.fee_vault.pending_referral_lamports);
+        market.fee_vault.pending_referral_lamports -= amount;
+        amount
+    };
+
+    pay_from_market(
+        &ctx.accounts.market.to_account_info(),
+        &referral_info,
+        amount,
+    )?;
+
+    emit_cpi!(TokenMillFeeSweepEvent {
+        market: ctx.accounts.market.key(),
+        recipient: ctx.accounts.referral_account.key(),
+        amount,
+    });
+
+    Ok(())
+}
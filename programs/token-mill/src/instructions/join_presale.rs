@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    merkle::verify_proof,
+    state::{Market, WalletPosition, WALLET_POSITION_PDA_SEED},
+};
+
+#[derive(Accounts)]
+pub struct JoinPresale<'info> {
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [WALLET_POSITION_PDA_SEED.as_bytes(), market.key().as_ref(), user.key().as_ref()],
+        bump = wallet_position.bump,
+    )]
+    pub wallet_position: Account<'info, WalletPosition>,
+
+    pub user: Signer<'info>,
+}
+
+/// Allow-lists the position of the user for the presale of the market. The leaf at `index` of
+/// the presale Merkle tree is the user's address.
+pub fn handler(ctx: Context<JoinPresale>, index: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+    let market = ctx.accounts.market.load()?;
+
+    if !market.launch_window.has_presale() {
+        return Err(error!(TokenMillError::InvalidPresaleProof));
+    }
+
+    let user = ctx.accounts.user.key();
+    if !verify_proof(
+        user.as_ref(),
+        &proof,
+        &market.launch_window.presale_root,
+        index,
+    ) {
+        return Err(error!(TokenMillError::InvalidPresaleProof));
+    }
+
+    ctx.accounts.wallet_position.is_allow_listed = true;
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    errors::TokenMillError,
+    state::{Market, MARKET_LAYOUT_VERSION},
+};
+
+#[derive(Accounts)]
+pub struct MigrateMarketLayout<'info> {
+    /// Checked against `base_token_mint` once grown, a version 0 market is too short to load
+    /// beforehand
+    #[account(
+        mut,
+        realloc = 8 + Market::INIT_SPACE,
+        realloc::payer = payer,
+        realloc::zero = true,
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub base_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows a market created before `MARKET_LAYOUT_VERSION` to the current layout. The appended
+/// fields start zeroed, leaving the market without launch fee, buy limits or presale, and its
+/// circulating supply is recovered from the supply of the base token mint.
+pub fn handler(ctx: Context<MigrateMarketLayout>) -> Result<()> {
+    let mut market = ctx.accounts.market.load_mut()?;
+
+    if market.layout_version >= MARKET_LAYOUT_VERSION {
+        return Err(error!(TokenMillError::InvalidMarketState));
+    }
+    if market.base_token_mint != ctx.accounts.base_token_mint.key() {
+        return Err(error!(TokenMillError::InvalidMintAccount));
+    }
+
+    // Every base token minted is held outside of the market, except for its reserve
+    market.circulating_supply = ctx
+        .accounts
+        .base_token_mint
+        .supply
+        .checked_sub(market.base_reserve)
+        .ok_or(error!(TokenMillError::InvalidMarketState))?;
+    market.layout_version = MARKET_LAYOUT_VERSION;
+
+    Ok(())
+}
@@ -1,21 +1,35 @@
 pub mod admin;
 pub mod create_market;
 pub mod create_market_with_spl;
+pub mod create_trader_stats;
+pub mod create_wallet_position;
 pub mod creator;
+pub mod fee_vault;
+pub mod join_presale;
+pub mod migrate_market_layout;
 pub mod permissioned_markets;
 pub mod referrals;
 pub mod staking;
 pub mod swap;
 pub mod purchase;
+pub mod quote_swap;
+pub mod sell;
 pub mod vesting;
 
 pub use admin::*;
 pub use create_market::*;
 pub use create_market_with_spl::*;
+pub use create_trader_stats::*;
+pub use create_wallet_position::*;
 pub use creator::*;
+pub use fee_vault::*;
+pub use join_presale::*;
+pub use migrate_market_layout::*;
 pub use permissioned_markets::*;
 pub use referrals::*;
 pub use staking::*;
 pub use swap::*;
 pub use purchase::*;
+pub use quote_swap::*;
+pub use sell::*;
 pub use vesting::*;
@@ -1,28 +1,70 @@
 use anchor_lang::prelude::*;
 
 use crate::{
+    errors::TokenMillError,
     events::TokenMillMarketFreedEvent,
-    state::{Market, SWAP_AUTHORITY_BADGE_PDA_SEED},
+    state::{Market, SwapDirections, SWAP_AUTHORITY_BADGE_PDA_SEED},
     SwapAuthorityBadge,
 };
 
 #[event_cpi]
 #[derive(Accounts)]
+#[instruction(lock_authority: Pubkey)]
 pub struct FreeMarket<'info> {
-    #[account(mut)]
+    #[account(mut, has_one = creator @ TokenMillError::InvalidAuthority)]
     pub market: AccountLoader<'info, Market>,
 
+    /// Badge issued to `lock_authority` by `lock_market`
     #[account(
         mut,
-        seeds = [SWAP_AUTHORITY_BADGE_PDA_SEED.as_bytes(), market.key().as_ref(), swap_authority.key().as_ref()],
+        seeds = [SWAP_AUTHORITY_BADGE_PDA_SEED.as_bytes(), market.key().as_ref(), lock_authority.as_ref()],
         bump = swap_authority_badge.bump,
+        constraint = swap_authority_badge.is_lock_authority @ TokenMillError::InvalidAuthority,
+        close = creator,
     )]
     pub swap_authority_badge: Account<'info, SwapAuthorityBadge>,
 
-    pub swap_authority: Signer<'info>,
+    pub authority: Signer<'info>,
+
+    /// CHECK: refunded the rent of the badge it paid in `lock_market`, checked against
+    /// `market.creator`
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
 }
 
-pub fn handler(ctx: Context<FreeMarket>) -> Result<()> {
+/// Opens the market to `buy`, `sell` and `swap`, closes the badge of the swap authority that
+/// locked it and invalidates every other badge. Only the creator, or the swap authority that
+/// locked the market with full trading rights, can free it.
+pub fn handler(ctx: Context<FreeMarket>, lock_authority: Pubkey) -> Result<()> {
+    {
+        let mut market = ctx.accounts.market.load_mut()?;
+
+        if market.is_permissioned == 0 {
+            return Err(error!(TokenMillError::InvalidMarketState));
+        }
+
+        let authority = ctx.accounts.authority.key();
+        if authority != market.creator {
+            let swap_authority_badge = &ctx.accounts.swap_authority_badge;
+
+            if authority != lock_authority
+                || swap_authority_badge.scope.directions != SwapDirections::Both
+            {
+                return Err(error!(TokenMillError::InvalidAuthority));
+            }
+
+            swap_authority_badge
+                .assert_active(market.swap_authority_epoch, Clock::get()?.unix_timestamp)?;
+        }
+
+        market.is_permissioned = 0;
+        // Badges left open become stale, including if the market is locked again
+        market.swap_authority_epoch = market
+            .swap_authority_epoch
+            .checked_add(1)
+            .ok_or(TokenMillError::MathError)?;
+    }
+
     emit_cpi!(TokenMillMarketFreedEvent {
         market: ctx.accounts.market.key(),
     });
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError,
+    events::TokenMillSwapAuthorityGrantEvent,
+    state::{Market, SwapAuthorityScope},
+    SwapAuthorityBadge, SWAP_AUTHORITY_BADGE_PDA_SEED,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct GrantSwapAuthority<'info> {
+    #[account(has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        init,
+        seeds = [SWAP_AUTHORITY_BADGE_PDA_SEED.as_bytes(), market.key().as_ref(), authority.as_ref()],
+        bump,
+        payer = creator,
+        space = 8 + SwapAuthorityBadge::INIT_SPACE
+    )]
+    pub swap_authority_badge: Account<'info, SwapAuthorityBadge>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Adds a swap authority to a market locked by `lock_market`.
+pub fn handler(
+    ctx: Context<GrantSwapAuthority>,
+    authority: Pubkey,
+    scope: SwapAuthorityScope,
+) -> Result<()> {
+    let swap_authority_epoch = {
+        let market = ctx.accounts.market.load()?;
+
+        if market.is_permissioned == 0 {
+            return Err(error!(TokenMillError::InvalidMarketState));
+        }
+
+        market.swap_authority_epoch
+    };
+    scope.validate()?;
+
+    let swap_authority_badge = &mut ctx.accounts.swap_authority_badge;
+    swap_authority_badge.bump = ctx.bumps.swap_authority_badge;
+    swap_authority_badge.scope = scope;
+    swap_authority_badge.epoch = swap_authority_epoch;
+
+    emit_cpi!(TokenMillSwapAuthorityGrantEvent {
+        market: ctx.accounts.market.key(),
+        swap_authority: authority,
+        scope,
+    });
+
+    Ok(())
+}
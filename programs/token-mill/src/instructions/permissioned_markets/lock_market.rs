@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
 
 use crate::{
-    errors::TokenMillError, events::TokenMillMarketLockedEvent, state::Market, SwapAuthorityBadge,
-    SWAP_AUTHORITY_BADGE_PDA_SEED,
+    errors::TokenMillError,
+    events::TokenMillMarketLockedEvent,
+    state::{Market, SwapAuthorityScope},
+    SwapAuthorityBadge, SWAP_AUTHORITY_BADGE_PDA_SEED,
 };
 
 #[event_cpi]
@@ -28,10 +30,35 @@ pub struct LockMarket<'info> {
 }
 
 // A market can only be locked upon creation, when the circulating supply is still 0
-pub fn handler(ctx: Context<LockMarket>, _authority: Pubkey) -> Result<()> {
+pub fn handler(
+    ctx: Context<LockMarket>,
+    authority: Pubkey,
+    scope: SwapAuthorityScope,
+) -> Result<()> {
+    scope.validate()?;
+
+    let swap_authority_epoch = {
+        let mut market = ctx.accounts.market.load_mut()?;
+
+        if market.circulating_supply != 0 || market.is_permissioned != 0 {
+            return Err(error!(TokenMillError::InvalidMarketState));
+        }
+
+        market.is_permissioned = 1;
+
+        market.swap_authority_epoch
+    };
+
+    let swap_authority_badge = &mut ctx.accounts.swap_authority_badge;
+    swap_authority_badge.bump = ctx.bumps.swap_authority_badge;
+    swap_authority_badge.scope = scope;
+    swap_authority_badge.epoch = swap_authority_epoch;
+    swap_authority_badge.is_lock_authority = true;
+
     emit_cpi!(TokenMillMarketLockedEvent {
         market: ctx.accounts.market.key(),
-        swap_authority: ctx.accounts.swap_authority_badge.key(),
+        swap_authority: authority,
+        scope,
     });
 
     Ok(())
@@ -1,7 +1,11 @@
 pub mod free_market;
+pub mod grant_swap_authority;
 pub mod lock_market;
 pub mod permissioned_swap;
+pub mod revoke_swap_authority;
 
 pub use free_market::*;
+pub use grant_swap_authority::*;
 pub use lock_market::*;
 pub use permissioned_swap::*;
+pub use revoke_swap_authority::*;
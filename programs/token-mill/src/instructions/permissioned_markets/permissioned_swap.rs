@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
     errors::TokenMillError,
     events::TokenMillSwapEvent,
-    state::{Market, SWAP_AUTHORITY_BADGE_PDA_SEED},
+    instructions::swap::{execute_swap, SwapAccounts},
+    state::{Market, WalletPosition, SWAP_AUTHORITY_BADGE_PDA_SEED, WALLET_POSITION_PDA_SEED},
     SwapAmountType, SwapAuthorityBadge, SwapType, TokenMillConfig,
 };
 
@@ -17,11 +19,14 @@ pub struct PermissionedSwap<'info> {
         mut,
         has_one = config @ TokenMillError::InvalidConfigAccount,
         has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
-        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount,
+        // Lamport markets hold no quote reserve and trade through `buy` and `sell`
+        constraint = market.load()?.quote_token_mint != native_mint::ID @ TokenMillError::InvalidQuoteTokenMint
     )]
     pub market: AccountLoader<'info, Market>,
 
     #[account(
+        mut,
         seeds = [SWAP_AUTHORITY_BADGE_PDA_SEED.as_bytes(), market.key().as_ref(), swap_authority.key().as_ref()],
         bump = swap_authority_badge.bump,
     )]
@@ -69,30 +74,112 @@ pub struct PermissionedSwap<'info> {
     pub swap_authority: Signer<'info>,
     pub user: Signer<'info>,
 
+    /// Optional token account receiving the output of the swap instead of the user's
+    #[account(mut)]
+    pub recipient_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional position of the user on the market, required before the market opens and on buys
+    /// when the market caps wallets
+    #[account(
+        mut,
+        seeds = [WALLET_POSITION_PDA_SEED.as_bytes(), market.key().as_ref(), user.key().as_ref()],
+        bump = wallet_position.bump,
+    )]
+    pub wallet_position: Option<Account<'info, WalletPosition>>,
+
     pub base_token_program: Interface<'info, TokenInterface>,
 
     pub quote_token_program: Interface<'info, TokenInterface>,
 }
 
+impl<'info> PermissionedSwap<'info> {
+    pub fn swap_accounts(&self) -> SwapAccounts<'_, 'info> {
+        SwapAccounts {
+            config: &self.config,
+            market: &self.market,
+            base_token_mint: &self.base_token_mint,
+            quote_token_mint: &self.quote_token_mint,
+            market_base_token_ata: &self.market_base_token_ata,
+            market_quote_token_ata: &self.market_quote_token_ata,
+            user_base_token_account: &self.user_base_token_account,
+            user_quote_token_account: &self.user_quote_token_account,
+            protocol_quote_token_ata: &self.protocol_quote_token_ata,
+            referral_token_account: self.referral_token_account.as_ref(),
+            recipient_token_account: self.recipient_token_account.as_ref(),
+            user: &self.user,
+            base_token_program: &self.base_token_program,
+            quote_token_program: &self.quote_token_program,
+        }
+    }
+}
+
+/// Swaps on a locked market with the co-signature of its swap authority, along the same curve
+/// and fee split as `swap`. The swap authority can charge `fee_override_bps` of the creator and
+/// staking fees, the protocol fee being charged in full, and route the output to `recipient_token_account`, within the
+/// expiry, directions and volume cap of its badge.
 pub fn handler(
     ctx: Context<PermissionedSwap>,
     swap_type: SwapType,
-    _swap_amount_type: SwapAmountType,
-    _amount: u64,
-    _other_amount_threshold: u64,
+    swap_amount_type: SwapAmountType,
+    amount: u64,
+    other_amount_threshold: u64,
+    fee_override_bps: Option<u16>,
 ) -> Result<(u64, u64)> {
+    // Badges left after `free_market` can't trade on the freed market
+    let swap_authority_epoch = {
+        let market = ctx.accounts.market.load()?;
+
+        if market.is_permissioned == 0 {
+            return Err(error!(TokenMillError::UnauthorizedMarket));
+        }
+
+        market.swap_authority_epoch
+    };
+
+    let (base_amount, quote_amount, fees) = execute_swap(
+        &ctx.accounts.swap_accounts(),
+        swap_type,
+        swap_amount_type,
+        amount,
+        other_amount_threshold,
+        // Permissioned markets are priced by their swap authority, no trader discount applies
+        0,
+        fee_override_bps,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    ctx.accounts.swap_authority_badge.record_trade(
+        swap_type,
+        base_amount,
+        swap_authority_epoch,
+        now,
+    )?;
+
+    // Launch buy caps and the opening schedule apply to swap authorities as well
+    ctx.accounts.market.load()?.record_position_trade(
+        swap_type,
+        base_amount,
+        ctx.accounts.wallet_position.as_deref_mut(),
+        now,
+    )?;
+
     emit_cpi!(TokenMillSwapEvent {
         user: ctx.accounts.user.key(),
         market: ctx.accounts.market.key(),
         swap_type,
-        base_amount: 0,
-        quote_amount: 0,
-        referral_token_account: None,
-        creator_fee: 0,
-        staking_fee: 0,
-        protocol_fee: 0,
-        referral_fee: 0,
+        base_amount,
+        quote_amount,
+        referral_token_account: ctx
+            .accounts
+            .referral_token_account
+            .as_ref()
+            .map(|a| a.key()),
+        creator_fee: fees.creator_fee,
+        staking_fee: fees.staking_fee,
+        protocol_fee: fees.protocol_fee,
+        referral_fee: fees.referral_fee,
+        launch_fee: fees.launch_fee,
     });
 
-    Ok((0, 0))
+    Ok((base_amount, quote_amount))
 }
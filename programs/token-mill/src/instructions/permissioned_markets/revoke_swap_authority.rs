@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::TokenMillError, events::TokenMillSwapAuthorityRevokeEvent, state::Market,
+    SwapAuthorityBadge, SWAP_AUTHORITY_BADGE_PDA_SEED,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(authority: Pubkey)]
+pub struct RevokeSwapAuthority<'info> {
+    #[account(has_one = creator @ TokenMillError::InvalidAuthority)]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        mut,
+        seeds = [SWAP_AUTHORITY_BADGE_PDA_SEED.as_bytes(), market.key().as_ref(), authority.as_ref()],
+        bump = swap_authority_badge.bump,
+        // The badge of the lock authority is closed by `free_market`
+        constraint = !swap_authority_badge.is_lock_authority @ TokenMillError::InvalidAuthority,
+        close = creator,
+    )]
+    pub swap_authority_badge: Account<'info, SwapAuthorityBadge>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+}
+
+/// Closes the badge of a swap authority, the market stays locked until `free_market`.
+pub fn handler(ctx: Context<RevokeSwapAuthority>, authority: Pubkey) -> Result<()> {
+    emit_cpi!(TokenMillSwapAuthorityRevokeEvent {
+        market: ctx.accounts.market.key(),
+        swap_authority: authority,
+    });
+
+    Ok(())
+}
@@ -1,24 +1,42 @@
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::{program::invoke, system_instruction};
+use anchor_spl::token::spl_token::native_mint;
 use anchor_spl::token_2022::{self as token, MintTo};
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 use crate::{
-    constant::PRICES_LENGTH,
+    curve,
     errors::TokenMillError,
     events::TokenMillSwapEvent,
-    state::{Market, TokenMillConfig, MARKET_PDA_SEED},
+    instructions::swap::{
+        amount_before_launch_fee, compute_fees, compute_launch_fee, launch_fee_bps, SwapFees,
+    },
+    state::{
+        DiscountSchedule, Market, StakePosition, TokenMillConfig, TraderStats, WalletPosition,
+        DISCOUNT_SCHEDULE_PDA_SEED, MARKET_PDA_SEED, TRADER_STATS_PDA_SEED,
+        WALLET_POSITION_PDA_SEED,
+    },
+    security::assert_deadline,
+    Deadline,
     ReferralAccount,
+    SwapAmountType,
+    SwapType,
     REFERRAL_ACCOUNT_PDA_SEED,
 };
-use crate::discount::compute_discount_bp;
+use crate::discount::{compute_trader_discount_bp, DiscountAccounts};
 
 #[event_cpi]
 #[derive(Accounts)]
 pub struct Purchase<'info> {
     pub config: Account<'info, TokenMillConfig>,
 
-    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount, has_one = base_token_mint @ TokenMillError::InvalidMintAccount)]
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        // Only lamport markets are bought with SOL, SPL-quoted markets trade through `swap`
+        constraint = market.load()?.quote_token_mint == native_mint::ID @ TokenMillError::InvalidQuoteTokenMint
+    )]
     pub market: AccountLoader<'info, Market>,
 
     pub base_token_mint: InterfaceAccount<'info, Mint>,
@@ -30,21 +48,39 @@ pub struct Purchase<'info> {
     #[account(mut, associated_token::mint = base_token_mint, associated_token::authority = buyer, associated_token::token_program = token_program)]
     pub buyer_base_token_ata: InterfaceAccount<'info, TokenAccount>,
 
-    /// The recipient that will receive creator fees (lamports)
-    #[account(mut)]
-    pub creator: UncheckedAccount<'info>,
-
-    /// Optional referral PDA (if present, referral fees are credited to this PDA)
-    #[account(mut)]
+    /// Optional referral PDA (if present, referral fees are credited to this PDA and swept to it later)
+    #[account(mut, constraint = referral_account.config == config.key() @ TokenMillError::InvalidReferralAccount)]
     pub referral_account: Option<Account<'info, ReferralAccount>>,
 
-    /// Optional referrer SOL account to receive referral share (fallback)
-    #[account(mut)]
-    pub referrer: Option<UncheckedAccount<'info>>,
-
-    /// Protocol fee recipient (from config)
-    #[account(mut, address = config.protocol_fee_recipient @ TokenMillError::InvalidAuthority)]
-    pub protocol_fee_recipient: UncheckedAccount<'info>,
+    /// Optional rolling volume of the buyer, required for volume-based fee discounts
+    #[account(
+        mut,
+        seeds = [TRADER_STATS_PDA_SEED.as_bytes(), config.key().as_ref(), buyer.key().as_ref()],
+        bump = trader_stats.bump,
+    )]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+
+    /// Optional discount tiers of the config, without them no discount applies
+    #[account(
+        seeds = [DISCOUNT_SCHEDULE_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump = discount_schedule.bump,
+    )]
+    pub discount_schedule: Option<Account<'info, DiscountSchedule>>,
+
+    /// Optional token account of the buyer holding the mint of a `TokenHolding` discount strategy
+    pub discount_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional stake position of the buyer on the market of a `StakedBalance` discount strategy
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Optional position of the buyer on the market, required before the market opens and when
+    /// the market caps wallets
+    #[account(
+        mut,
+        seeds = [WALLET_POSITION_PDA_SEED.as_bytes(), market.key().as_ref(), buyer.key().as_ref()],
+        bump = wallet_position.bump,
+    )]
+    pub wallet_position: Option<Account<'info, WalletPosition>>,
 
     #[account(mut)]
     pub buyer: Signer<'info>,
@@ -55,20 +91,23 @@ pub struct Purchase<'info> {
 }
 
 /// Simple purchase handler. This enforces a SOL transfer from buyer -> market PDA (treasury)
-/// before minting base tokens to the buyer. It also immediately distributes fees (creator,
-/// protocol, referral) from the market PDA to recipients. Pricing is derived from the
-/// market's `ask_prices[0]` as a simple per-token price (placeholder bonding curve).
+/// before minting base tokens to the buyer. Fees (creator, protocol, referral) stay in the
+/// market PDA, tracked by its fee vault counters until swept to their recipients. Pricing follows the market's ask
+/// curve (see `crate::curve`).
 ///
 /// NOTE: This implementation makes the following assumptions (documented here):
 /// - Fee shares are specified in basis points (parts per 10_000). If your config uses a
 ///   different scale, adapt the math accordingly.
-/// - `market.ask_prices[0]` holds the price-per-token in the same quote units as SOL lamports.
-///   In practice you should adapt `compute_price` to your bonding curve and decimals.
+/// - `market.ask_prices` are expressed in lamports per whole base token.
 pub fn handler(
     ctx: Context<Purchase>,
-    swap_amount_type: u8, // 0 = ExactInput (quote lamports), 1 = ExactOutput (base tokens)
+    swap_amount_type: SwapAmountType, // ExactInput = quote lamports, ExactOutput = base tokens
     amount: u64,
+    other_amount_threshold: u64, // min base tokens out (ExactInput) or max lamports in (ExactOutput)
+    deadline: Option<Deadline>,
 ) -> Result<(u64, u64)> {
+    assert_deadline(deadline)?;
+
     let mut market = ctx.accounts.market.load_mut()?;
 
     // Validate market PDA ownership: ensure the provided `market` is the expected PDA
@@ -87,97 +126,81 @@ pub fn handler(
     if market.is_migrated != 0 {
         return Err(error!(TokenMillError::MarketMigrated));
     }
-    // Bonding curve pricing (linear curve): price(s) = base_price + width * s
-    // Total cost to mint q tokens from supply s0: cost = base_price*q + width*(2*s0*q + q*q)/2
-    // We'll use integer math with u128 to avoid overflow.
-    let base_price: u128 = market.ask_prices[0] as u128; // price-per-token at supply 0
-    let width: u128 = market.width_scaled as u128; // linear slope per token
-    let s0: u128 = market.total_supply as u128;
-
-    // helper: integer sqrt
-    fn integer_sqrt(n: u128) -> u128 {
-        if n <= 1 { return n; }
-        let mut x0 = n / 2;
-        let mut x1 = (x0 + n / x0) / 2;
-        while x1 < x0 {
-            x0 = x1;
-            x1 = (x0 + n / x0) / 2;
-        }
-        x0
+    market.assert_not_paused(&ctx.accounts.config)?;
+    // Locked markets only trade through `permissioned_swap`
+    if market.is_permissioned != 0 {
+        return Err(error!(TokenMillError::UnauthorizedMarket));
     }
+    // Price the purchase on the market's ask curve, shared with `swap` and `permissioned_swap`.
+    // During the launch phase the launch fee is paid on top, so an exact input budget covers both.
+    let clock = Clock::get()?;
+    let launch_fee_bps = launch_fee_bps(&market, SwapType::Buy, &clock);
+    let (base_amount, quote_amount, fee) = curve::get_swap_amounts(
+        &market,
+        SwapType::Buy,
+        swap_amount_type,
+        amount_before_launch_fee(amount, SwapType::Buy, swap_amount_type, launch_fee_bps),
+    )?;
+    let launch_fee = compute_launch_fee(quote_amount, launch_fee_bps);
+
+    // Compute the buyer's discount before this trade, its volume is recorded below
+    let now = clock.unix_timestamp;
+    let discount_bp = compute_trader_discount_bp(
+        &ctx.accounts.config,
+        &ctx.accounts.buyer.key(),
+        &DiscountAccounts {
+            discount_schedule: ctx.accounts.discount_schedule.as_deref(),
+            trader_stats: ctx.accounts.trader_stats.as_deref(),
+            token_account: ctx.accounts.discount_token_account.as_ref(),
+            stake_position: ctx.accounts.stake_position.as_deref(),
+        },
+        now,
+    )?;
+
+    // Fees are taken out of the ask/bid spread, shared with `sell`
+    let fees = compute_lamport_fees(
+        &ctx.accounts.config,
+        &market,
+        fee,
+        ctx.accounts.referral_account.is_some(),
+        discount_bp,
+    )?;
+
+    // The waived part of the fee is never charged to the buyer
+    let paid_amount = quote_amount
+        .checked_sub(fees.discount)
+        .and_then(|amount| amount.checked_add(launch_fee))
+        .ok_or(error!(TokenMillError::MathOverflow))?;
 
-    // Compute base_amount (tokens to mint) and quote_amount (lamports to charge)
-    let (quote_amount, base_amount) = match swap_amount_type {
-        1 => {
-            // ExactOutput: amount is desired base tokens q
-            let q = amount as u128;
-            if q == 0 { return Err(error!(TokenMillError::InvalidAmount)); }
-            // cost = base_price*q + width*(2*s0*q + q*q)/2
-            let term1 = base_price.checked_mul(q).ok_or(error!(TokenMillError::MathOverflow))?;
-            let term2_numer = width
-                .checked_mul(2u128.checked_mul(s0).ok_or(error!(TokenMillError::MathOverflow))?.checked_mul(q).ok_or(error!(TokenMillError::MathOverflow))?
-                    .checked_add(width.checked_mul(q.checked_mul(q).ok_or(error!(TokenMillError::MathOverflow))?).ok_or(error!(TokenMillError::MathOverflow))?).ok_or(error!(TokenMillError::MathOverflow))?)
-                .ok_or(error!(TokenMillError::MathOverflow));
-            // To avoid repeated complexity, compute term2 as width*(2*s0*q + q*q)/2 safely
-            let two_s0_q = 2u128.checked_mul(s0).ok_or(error!(TokenMillError::MathOverflow))?.checked_mul(q).ok_or(error!(TokenMillError::MathOverflow))?;
-            let q_sq = q.checked_mul(q).ok_or(error!(TokenMillError::MathOverflow))?;
-            let numerator = width.checked_mul(two_s0_q.checked_add(q_sq).ok_or(error!(TokenMillError::MathOverflow))?).ok_or(error!(TokenMillError::MathOverflow))?;
-            let term2 = numerator.checked_div(2u128).ok_or(error!(TokenMillError::MathOverflow))?;
-            let cost = term1.checked_add(term2).ok_or(error!(TokenMillError::MathOverflow))?;
-            // ensure cost fits u64 lamports (Solana lamports fit in u64)
-            if cost == 0 { return Err(error!(TokenMillError::InvalidAmount)); }
-            (cost as u64, q as u64)
-        }
-        0 => {
-            // ExactInput: amount is quote lamports; need to solve quadratic for q
-            let quote = amount as u128;
-            if quote == 0 { return Err(error!(TokenMillError::InvalidAmount)); }
-            // Solve a*q^2 + b*q - quote = 0 where:
-            // a = width/2
-            // b = width*s0 + base_price
-            // For integer math, multiply equation by 2: (width)*q^2 + 2*(width*s0 + base_price)*q - 2*quote = 0
-            let a = width; // corresponds to (width) after multiply-by-2
-            let b = 2u128.checked_mul(width.checked_mul(s0).ok_or(error!(TokenMillError::MathOverflow))?.checked_add(base_price).ok_or(error!(TokenMillError::MathOverflow))?).ok_or(error!(TokenMillError::MathOverflow))?;
-            let c = quote.checked_mul(2u128).ok_or(error!(TokenMillError::MathOverflow))?;
-
-            // discriminant = b^2 + 4*a*c
-            let disc = b.checked_mul(b).ok_or(error!(TokenMillError::MathOverflow))?.checked_add(4u128.checked_mul(a).ok_or(error!(TokenMillError::MathOverflow))?.checked_mul(c).ok_or(error!(TokenMillError::MathOverflow))?).ok_or(error!(TokenMillError::MathOverflow))?;
-            let sqrt_disc = integer_sqrt(disc);
-            // positive root: (-b + sqrt_disc) / (2a)
-            if a == 0 {
-                // width == 0 -> flat price: base_price
-                let q = quote.checked_div(base_price).ok_or(error!(TokenMillError::MathOverflow))?;
-                if q == 0 { return Err(error!(TokenMillError::InvalidAmount)); }
-                return Ok((quote as u64, q as u64));
-            }
-            let numerator = if sqrt_disc > b { sqrt_disc - b } else { 0 };
-            let denom = 2u128.checked_mul(a).ok_or(error!(TokenMillError::MathOverflow))?;
-            let q = numerator.checked_div(denom).ok_or(error!(TokenMillError::MathOverflow))?; // floor
-            if q == 0 { return Err(error!(TokenMillError::InvalidAmount)); }
-
-            // compute cost for q tokens to ensure not exceeding quote (rounding)
-            let term1 = base_price.checked_mul(q).ok_or(error!(TokenMillError::MathOverflow))?;
-            let two_s0_q = 2u128.checked_mul(s0).ok_or(error!(TokenMillError::MathOverflow))?.checked_mul(q).ok_or(error!(TokenMillError::MathOverflow))?;
-            let q_sq = q.checked_mul(q).ok_or(error!(TokenMillError::MathOverflow))?;
-            let numerator2 = width.checked_mul(two_s0_q.checked_add(q_sq).ok_or(error!(TokenMillError::MathOverflow))?).ok_or(error!(TokenMillError::MathOverflow))?;
-            let term2 = numerator2.checked_div(2u128).ok_or(error!(TokenMillError::MathOverflow))?;
-            let cost = term1.checked_add(term2).ok_or(error!(TokenMillError::MathOverflow))?;
-
-            if cost == 0 { return Err(error!(TokenMillError::InvalidAmount)); }
-            (cost as u64, q as u64)
-        }
-        _ => return Err(error!(TokenMillError::InvalidSwapType)),
+    // Slippage protection
+    let threshold_met = match swap_amount_type {
+        SwapAmountType::ExactInput => base_amount >= other_amount_threshold,
+        SwapAmountType::ExactOutput => paid_amount <= other_amount_threshold,
     };
+    if !threshold_met {
+        return Err(error!(TokenMillError::AmountThresholdNotMet));
+    }
 
-    // Compute wallet-based discount (based on buyer's current lamports before transfer)
-    let buyer_balance_before = ctx.accounts.buyer.to_account_info().lamports();
-    let discount_bp = compute_discount_bp(buyer_balance_before);
+    // Enforce the opening schedule and the launch buy caps on the minted amount
+    market.record_position_trade(
+        SwapType::Buy,
+        base_amount,
+        ctx.accounts.wallet_position.as_deref_mut(),
+        clock.unix_timestamp,
+    )?;
+
+    // Purchased tokens are minted rather than drawn from the reserve
+    market.increase_circulating_supply(base_amount)?;
+
+    if let Some(trader_stats) = &mut ctx.accounts.trader_stats {
+        trader_stats.record_volume(quote_amount, now)?;
+    }
 
     // Transfer SOL from buyer -> market PDA (treasury)
     let market_info = ctx.accounts.market.to_account_info();
     let market_key = market_info.key();
     let market_lamports_before = market_info.lamports();
-    let ix = system_instruction::transfer(&ctx.accounts.buyer.key(), market_key, quote_amount);
+    let ix = system_instruction::transfer(&ctx.accounts.buyer.key(), market_key, paid_amount);
     invoke(&ix, &[
         ctx.accounts.buyer.to_account_info(),
         market_info.clone(),
@@ -186,7 +209,7 @@ pub fn handler(
 
     // Verify the market PDA received the SOL payment before minting tokens
     let market_lamports_after = ctx.accounts.market.to_account_info().lamports();
-    if market_lamports_after != market_lamports_before.checked_add(quote_amount).ok_or(error!(TokenMillError::MathOverflow))? {
+    if market_lamports_after != market_lamports_before.checked_add(paid_amount).ok_or(error!(TokenMillError::MathOverflow))? {
         return Err(error!(TokenMillError::InvalidMarketState));
     }
 
@@ -194,57 +217,20 @@ pub fn handler(
     emit_cpi!(crate::events::TokenMillPaymentEvent {
         user: ctx.accounts.buyer.key(),
         market: ctx.accounts.market.key(),
-        quote_amount,
+        quote_amount: paid_amount,
         base_amount,
     });
 
-    // Fee calculations (basis points / 10_000)
-    let bp_denom: u128 = 10_000;
-    let quote_u128 = quote_amount as u128;
-    let protocol_bp = ctx.accounts.config.default_protocol_fee_share as u128;
-    let referral_bp = ctx.accounts.config.referral_fee_share as u128;
-
-    // protocol fee total from the whole quote_amount
-    let protocol_fee_total = quote_u128
-        .checked_mul(protocol_bp)
-        .ok_or(error!(TokenMillError::MathOverflow))?
-        .checked_div(bp_denom)
-        .ok_or(error!(TokenMillError::MathOverflow))?;
-
-    // apply wallet-based discount to protocol fees
-    let discount_amount = protocol_fee_total
-        .checked_mul(discount_bp)
-        .ok_or(error!(TokenMillError::MathOverflow))?
-        .checked_div(bp_denom)
-        .ok_or(error!(TokenMillError::MathOverflow))?;
-    let protocol_fee_total_after_discount = protocol_fee_total.checked_sub(discount_amount).ok_or(error!(TokenMillError::MathOverflow))?;
-
-    // referral is a portion of protocol fee after discount
-    let referral_fee = protocol_fee_total_after_discount
-        .checked_mul(referral_bp)
-        .ok_or(error!(TokenMillError::MathOverflow))?
-        .checked_div(bp_denom)
-        .ok_or(error!(TokenMillError::MathOverflow))?;
-
-    let protocol_fee_net = protocol_fee_total_after_discount.checked_sub(referral_fee).ok_or(error!(TokenMillError::MathOverflow))?;
-
-    // creator fee from market.fees (shares are basis points of total). Staking logic removed.
-    let creator_fee = quote_u128
-        .checked_mul(market.fees.creator_fee_share as u128)
-        .ok_or(error!(TokenMillError::MathOverflow))?
-        .checked_div(bp_denom)
-        .ok_or(error!(TokenMillError::MathOverflow))?;
-
-    let staking_fee: u128 = 0u128; // staking removed — keep for backwards compatibility in events
+    let protocol_fee_net = fees.protocol_fee as u128;
+    let referral_fee = fees.referral_fee as u128;
+    let creator_fee = fees.creator_fee as u128;
+    let staking_fee = fees.staking_fee as u128;
 
-    // Track pending creator fees inside market in lamports
-    market.fees.pending_creator_fees = market
-        .fees
-        .pending_creator_fees
-        .checked_add(creator_fee as u64)
-        .ok_or(error!(TokenMillError::MathOverflow))?;
+    // Fees stay in the market PDA and are withdrawn by the permissionless sweep instructions
+    accrue_lamport_fees(&mut market, &fees, ctx.accounts.referral_account.as_deref_mut())?;
+    market.launch_fee.start(&clock);
+    market.accrue_launch_fee(launch_fee, true)?;
 
-    // The market owns lamports; distribute protocol and referral immediately and keep creator staking pending
     // Build signer seeds for market PDA
     let bump = market.bump;
     let seeds: &[&[u8]] = &[
@@ -253,106 +239,6 @@ pub fn handler(
         &[bump],
     ];
 
-    // Payouts from market PDA: protocol_fee_net -> protocol_fee_recipient
-    if protocol_fee_net > 0 {
-        let ix = system_instruction::transfer(market_key, &ctx.accounts.protocol_fee_recipient.key(), protocol_fee_net as u64);
-        anchor_lang::solana_program::program::invoke_signed(
-            &ix,
-            &[
-                market_info.clone(),
-                ctx.accounts.protocol_fee_recipient.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[seeds],
-        )?;
-    }
-
-    // Referral payout: prefer crediting a referral PDA so referrer can claim later; otherwise pay referrer directly
-    if referral_fee > 0 {
-        // Preferred: credit referral PDA so referrer can claim later
-        if let Some(referral_acct) = &mut ctx.accounts.referral_account {
-            let ix = system_instruction::transfer(market_key, &referral_acct.to_account_info().key(), referral_fee as u64);
-            let res = anchor_lang::solana_program::program::invoke_signed(
-                &ix,
-                &[
-                    market_info.clone(),
-                    referral_acct.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-                &[seeds],
-            );
-            if res.is_err() {
-                // fallback to protocol fee recipient
-                let ix2 = system_instruction::transfer(market_key, &ctx.accounts.protocol_fee_recipient.key(), referral_fee as u64);
-                anchor_lang::solana_program::program::invoke_signed(
-                    &ix2,
-                    &[
-                        market_info.clone(),
-                        ctx.accounts.protocol_fee_recipient.to_account_info(),
-                        ctx.accounts.system_program.to_account_info(),
-                    ],
-                    &[seeds],
-                )?;
-            } else {
-                // update pending lamports bookkeeping on the referral account object
-                referral_acct.pending_lamports = referral_acct
-                    .pending_lamports
-                    .checked_add(referral_fee as u64)
-                    .ok_or(error!(TokenMillError::MathOverflow))?;
-            }
-        } else if let Some(referrer_acct) = &ctx.accounts.referrer {
-            let ix = system_instruction::transfer(market_key, &referrer_acct.key(), referral_fee as u64);
-            let res = anchor_lang::solana_program::program::invoke_signed(
-                &ix,
-                &[
-                    market_info.clone(),
-                    referrer_acct.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-                &[seeds],
-            );
-            if res.is_err() {
-                // fallback: send to protocol fee recipient to avoid failing the entire tx
-                let ix2 = system_instruction::transfer(market_key, &ctx.accounts.protocol_fee_recipient.key(), referral_fee as u64);
-                anchor_lang::solana_program::program::invoke_signed(
-                    &ix2,
-                    &[
-                        market_info.clone(),
-                        ctx.accounts.protocol_fee_recipient.to_account_info(),
-                        ctx.accounts.system_program.to_account_info(),
-                    ],
-                    &[seeds],
-                )?;
-            }
-        } else {
-            // fallback: send referral fee to protocol fee recipient (treasury)
-            let ix2 = system_instruction::transfer(market_key, &ctx.accounts.protocol_fee_recipient.key(), referral_fee as u64);
-            anchor_lang::solana_program::program::invoke_signed(
-                &ix2,
-                &[
-                    ctx.accounts.market.to_account_info(),
-                    ctx.accounts.protocol_fee_recipient.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-                &[seeds],
-            )?;
-        }
-    }
-
-    // Creator payout: transfer creator_fee immediately to `creator` account.
-    if creator_fee > 0 {
-        let ix = system_instruction::transfer(market_key, &ctx.accounts.creator.key(), creator_fee as u64);
-        anchor_lang::solana_program::program::invoke_signed(
-            &ix,
-            &[
-                market_info.clone(),
-                ctx.accounts.creator.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[seeds],
-        )?;
-    }
-
     // Mint base tokens to buyer using market PDA as mint authority
     let signer_seeds: &[&[&[u8]]] = &[seeds];
 
@@ -373,13 +259,73 @@ pub fn handler(
         market: ctx.accounts.market.key(),
         swap_type: crate::SwapType::Buy,
         base_amount,
-        quote_amount,
+        quote_amount: paid_amount,
         referral_token_account: None,
         creator_fee: creator_fee as u64,
         staking_fee: staking_fee as u64,
         protocol_fee: protocol_fee_net as u64,
         referral_fee: referral_fee as u64,
+        launch_fee,
     });
 
-    Ok((base_amount, quote_amount))
+    Ok((base_amount, paid_amount))
+}
+
+/// Credits the fees of a lamport trade to the market's fee vault counters. The referral fee is
+/// attributed to `referral_account` and backed by the referral pool, trades without a referral
+/// account leave it to the protocol.
+pub fn accrue_lamport_fees(
+    market: &mut Market,
+    fees: &SwapFees,
+    referral_account: Option<&mut ReferralAccount>,
+) -> Result<()> {
+    let fee_vault = &mut market.fee_vault;
+    let protocol_fee = match referral_account {
+        Some(referral_account) => {
+            referral_account.pending_lamports = referral_account
+                .pending_lamports
+                .checked_add(fees.referral_fee)
+                .ok_or(error!(TokenMillError::MathOverflow))?;
+            fee_vault.pending_referral_lamports = fee_vault
+                .pending_referral_lamports
+                .checked_add(fees.referral_fee)
+                .ok_or(error!(TokenMillError::MathOverflow))?;
+            fees.protocol_fee
+        }
+        None => fees
+            .protocol_fee
+            .checked_add(fees.referral_fee)
+            .ok_or(error!(TokenMillError::MathOverflow))?,
+    };
+
+    fee_vault.pending_protocol_lamports = fee_vault
+        .pending_protocol_lamports
+        .checked_add(protocol_fee)
+        .ok_or(error!(TokenMillError::MathOverflow))?;
+    fee_vault.pending_creator_lamports = fee_vault
+        .pending_creator_lamports
+        .checked_add(fees.creator_fee)
+        .ok_or(error!(TokenMillError::MathOverflow))?;
+
+    Ok(())
+}
+
+/// Fee split applied to lamport trades, taken out of the ask/bid spread `fee` like the fees of
+/// `swap` so the bid side of the curve stays backed by the lamports of the market. Lamport
+/// markets have no staking rewards, their staking share goes to the protocol.
+pub fn compute_lamport_fees(
+    config: &TokenMillConfig,
+    market: &Market,
+    fee: u64,
+    has_referral: bool,
+    discount_bp: u128,
+) -> Result<SwapFees> {
+    let mut fees = compute_fees(market, config, fee, has_referral, discount_bp)?;
+    fees.protocol_fee = fees
+        .protocol_fee
+        .checked_add(fees.staking_fee)
+        .ok_or(error!(TokenMillError::MathOverflow))?;
+    fees.staking_fee = 0;
+
+    Ok(fees)
 }
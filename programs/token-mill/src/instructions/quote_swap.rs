@@ -0,0 +1,232 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::spl_token::native_mint,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::{
+    curve,
+    discount::{compute_trader_discount_bp, DiscountAccounts},
+    errors::TokenMillError,
+    instructions::{
+        purchase::compute_lamport_fees,
+        swap::{
+            amount_before_launch_fee, apply_fee_discount, apply_launch_fee, compute_fees,
+            compute_launch_fee, compute_swap_amounts, launch_fee_bps, SwapAmounts,
+        },
+    },
+    state::{
+        DiscountSchedule, Market, StakePosition, TokenMillConfig, TraderStats, WalletPosition,
+        DISCOUNT_SCHEDULE_PDA_SEED,
+    },
+    SwapAmountType, SwapType,
+};
+
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub base_token_mint: InterfaceAccount<'info, Mint>,
+
+    pub quote_token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Optional trader the quote is priced for, required to quote holding and staking discounts
+    /// CHECK: only compared against the owner of the discount accounts
+    pub trader: Option<UncheckedAccount<'info>>,
+
+    /// Optional rolling volume of the trader, used for the volume-based discount. Must belong to
+    /// `trader`, as the seeds of `swap` require
+    #[account(has_one = config @ TokenMillError::InvalidConfigAccount)]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+
+    /// Optional discount tiers of the config, without them no discount is quoted
+    #[account(
+        seeds = [DISCOUNT_SCHEDULE_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump = discount_schedule.bump,
+    )]
+    pub discount_schedule: Option<Account<'info, DiscountSchedule>>,
+
+    /// Optional token account of the trader holding the mint of a `TokenHolding` discount strategy
+    pub discount_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional stake position of the trader on the market of a `StakedBalance` discount strategy
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Optional position of the trader on the market, checked against the opening schedule and
+    /// the buy caps like `swap` does, without being updated
+    pub wallet_position: Option<Account<'info, WalletPosition>>,
+
+    /// Optional referral account, quoting the referral split of `swap`
+    pub referral_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct SwapQuote {
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub creator_fee: u64,
+    pub staking_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+    /// Anti-sniper fee paid on top of a buy during the launch phase, included in `quote_amount`
+    pub launch_fee: u64,
+    /// Marginal price after the trade on the traded side of the curve, in quote units per whole
+    /// base token
+    pub spot_price: u64,
+}
+
+/// Prices a trade without touching any account. Markets quoted in wrapped SOL are quoted with the
+/// lamport fee split of `buy` and `sell`, every other market with the fee split of `swap`.
+pub fn handler(
+    ctx: Context<QuoteSwap>,
+    swap_type: SwapType,
+    swap_amount_type: SwapAmountType,
+    amount: u64,
+) -> Result<SwapQuote> {
+    let market = ctx.accounts.market.load()?;
+
+    if market.is_migrated != 0 {
+        return Err(error!(TokenMillError::MarketMigrated));
+    }
+    market.assert_not_paused(&ctx.accounts.config)?;
+
+    // Locked markets only trade through `permissioned_swap`
+    if market.is_permissioned != 0 {
+        return Err(error!(TokenMillError::UnauthorizedMarket));
+    }
+
+    let clock = Clock::get()?;
+    let trader = ctx
+        .accounts
+        .trader
+        .as_ref()
+        .map_or(Pubkey::default(), |trader| trader.key());
+    if let Some(trader_stats) = &ctx.accounts.trader_stats {
+        if trader_stats.user != trader {
+            return Err(error!(TokenMillError::InvalidDiscountAccount));
+        }
+    }
+    let discount_bp = compute_trader_discount_bp(
+        &ctx.accounts.config,
+        &trader,
+        &DiscountAccounts {
+            discount_schedule: ctx.accounts.discount_schedule.as_deref(),
+            trader_stats: ctx.accounts.trader_stats.as_deref(),
+            token_account: ctx.accounts.discount_token_account.as_ref(),
+            stake_position: ctx.accounts.stake_position.as_deref(),
+        },
+        clock.unix_timestamp,
+    )?;
+    let launch_fee_bps = launch_fee_bps(&market, swap_type, &clock);
+    let amount = amount_before_launch_fee(amount, swap_type, swap_amount_type, launch_fee_bps);
+
+    let (amounts, fees) = if market.quote_token_mint == native_mint::ID {
+        // Lamport markets mint and burn the base token and move lamports, no transfer fee applies
+        let (base_amount, quote_amount, fee) =
+            curve::get_swap_amounts(&market, swap_type, swap_amount_type, amount)?;
+        let mut fees = compute_lamport_fees(
+            &ctx.accounts.config,
+            &market,
+            fee,
+            ctx.accounts.referral_token_account.is_some(),
+            discount_bp,
+        )?;
+        fees.launch_fee = compute_launch_fee(quote_amount, launch_fee_bps);
+        // Like `buy`, the waived part of the fee is not charged to the buyer. Like `sell`, sells
+        // carry no fee and are paid the whole bid quote
+        let (quote_amount, fee) = match swap_type {
+            SwapType::Buy => (quote_amount.checked_sub(fees.discount), fee - fees.discount),
+            SwapType::Sell => (Some(quote_amount), fee),
+        };
+        let quote_amount = quote_amount
+            .and_then(|a| a.checked_add(fees.launch_fee))
+            .ok_or(error!(TokenMillError::MathError))?;
+        let amounts = SwapAmounts {
+            base_amount,
+            quote_amount,
+            market_base_amount: base_amount,
+            market_quote_amount: quote_amount,
+            fee,
+        };
+
+        (amounts, fees)
+    } else {
+        if let Some(referral_token_account) = &ctx.accounts.referral_token_account {
+            if referral_token_account.mint != market.quote_token_mint {
+                return Err(error!(TokenMillError::InvalidReferralAccount));
+            }
+        }
+        let mut amounts = compute_swap_amounts(
+            &market,
+            &ctx.accounts.base_token_mint,
+            &ctx.accounts.quote_token_mint,
+            swap_type,
+            swap_amount_type,
+            amount,
+        )?;
+        let mut fees = compute_fees(
+            &market,
+            &ctx.accounts.config,
+            amounts.fee,
+            ctx.accounts.referral_token_account.is_some(),
+            discount_bp,
+        )?;
+        apply_fee_discount(&mut amounts, &fees, &ctx.accounts.quote_token_mint)?;
+        apply_launch_fee(
+            &mut amounts,
+            &mut fees,
+            launch_fee_bps,
+            &ctx.accounts.quote_token_mint,
+        )?;
+
+        (amounts, fees)
+    };
+
+    // Quote trades the fill would reject, on a copy of the trader's position
+    let mut wallet_position = match &ctx.accounts.wallet_position {
+        Some(wallet_position) => {
+            if wallet_position.market != ctx.accounts.market.key() || wallet_position.user != trader
+            {
+                return Err(error!(TokenMillError::InvalidWalletPosition));
+            }
+            Some(WalletPosition::clone(wallet_position))
+        }
+        None => None,
+    };
+    market.record_position_trade(
+        swap_type,
+        amounts.base_amount,
+        wallet_position.as_mut(),
+        clock.unix_timestamp,
+    )?;
+
+    let new_supply = match swap_type {
+        SwapType::Buy => market
+            .circulating_supply
+            .checked_add(amounts.market_base_amount)
+            .ok_or(error!(TokenMillError::MathOverflow))?,
+        SwapType::Sell => market
+            .circulating_supply
+            .checked_sub(amounts.market_base_amount)
+            .ok_or(error!(TokenMillError::InvalidAmount))?,
+    };
+    let spot_price = curve::spot_price(&market, swap_type, new_supply)?;
+
+    Ok(SwapQuote {
+        base_amount: amounts.base_amount,
+        quote_amount: amounts.quote_amount,
+        creator_fee: fees.creator_fee,
+        staking_fee: fees.staking_fee,
+        protocol_fee: fees.protocol_fee,
+        referral_fee: fees.referral_fee,
+        launch_fee: fees.launch_fee,
+        spot_price,
+    })
+}
@@ -1,14 +1,13 @@
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::{system_instruction};
 use anchor_spl::token_2022 as token;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
-use crate::{events::TokenMillReferralFeeClaimEvent, ReferralAccount, REFERRAL_ACCOUNT_PDA_SEED};
+use crate::{events::TokenMillReferralFeeClaimEvent, lamports::pay_from_market, ReferralAccount, REFERRAL_ACCOUNT_PDA_SEED};
 
 #[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimReferralFees<'info> {
-    #[account(has_one = referrer)]
+    #[account(mut, has_one = referrer)]
     pub referral_account: Account<'info, ReferralAccount>,
 
     pub quote_token_mint: InterfaceAccount<'info, Mint>,
@@ -29,6 +28,7 @@ pub struct ClaimReferralFees<'info> {
     )]
     pub referrer_quote_token_ata: InterfaceAccount<'info, TokenAccount>,
 
+    #[account(mut)]
     pub referrer: Signer<'info>,
     pub quote_token_program: Interface<'info, TokenInterface>,
 
@@ -48,26 +48,20 @@ pub fn handler(ctx: Context<ClaimReferralFees>) -> Result<()> {
     ];
     let signer_seeds: &[&[&[u8]]] = &[seeds];
 
-    // 1) Claim pending lamports (SOL) if any
-    let pending_lamports = ctx.accounts.referral_account.pending_lamports;
+    // 1) Claim pending lamports (SOL) if any, up to what was swept in from the markets' referral pools
+    let referral_info = ctx.accounts.referral_account.to_account_info();
+    let min_balance = Rent::get()?.minimum_balance(referral_info.data_len());
+    let pending_lamports = ctx
+        .accounts
+        .referral_account
+        .pending_lamports
+        .min(referral_info.lamports().saturating_sub(min_balance));
     if pending_lamports > 0 {
-        let ix = system_instruction::transfer(
-            &ctx.accounts.referral_account.to_account_info().key(),
-            &ctx.accounts.referrer.key(),
-            pending_lamports,
-        );
-        anchor_lang::solana_program::program::invoke_signed(
-            &ix,
-            &[
-                ctx.accounts.referral_account.to_account_info(),
-                ctx.accounts.referrer.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            signer_seeds,
-        )?;
+        // The referral PDA holds data, so lamports are moved out of it directly
+        pay_from_market(&referral_info, &ctx.accounts.referrer.to_account_info(), pending_lamports)?;
         distributed = distributed.checked_add(pending_lamports).ok_or(error!(crate::errors::TokenMillError::MathOverflow))?;
-        // clear pending
-        ctx.accounts.referral_account.pending_lamports = 0u64;
+        // clear what was paid
+        ctx.accounts.referral_account.pending_lamports -= pending_lamports;
     }
 
     // 2) Claim any quote-token balance from referral ATA -> referrer ATA
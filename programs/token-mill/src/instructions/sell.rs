@@ -0,0 +1,177 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount};
+
+use crate::{
+    curve,
+    errors::TokenMillError,
+    events::TokenMillSwapEvent,
+    lamports::pay_from_market,
+    security::assert_deadline,
+    state::{
+        Market, TokenMillConfig, TraderStats, WalletPosition, MARKET_PDA_SEED,
+        TRADER_STATS_PDA_SEED, WALLET_POSITION_PDA_SEED,
+    },
+    Deadline, SwapAmountType, SwapType,
+};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Sell<'info> {
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_PDA_SEED.as_bytes(), base_token_mint.key().as_ref()],
+        bump,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
+        // Only lamport markets pay sellers in SOL, SPL-quoted markets trade through `swap`
+        constraint = market.load()?.quote_token_mint == native_mint::ID @ TokenMillError::InvalidQuoteTokenMint
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(mut)]
+    pub base_token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = base_token_mint,
+        associated_token::authority = seller,
+        associated_token::token_program = token_program
+    )]
+    pub seller_base_token_ata: InterfaceAccount<'info, TokenAccount>,
+
+    /// Optional rolling volume of the seller, recorded for volume-based fee discounts on buys
+    #[account(
+        mut,
+        seeds = [TRADER_STATS_PDA_SEED.as_bytes(), config.key().as_ref(), seller.key().as_ref()],
+        bump = trader_stats.bump,
+    )]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+
+    /// Optional position of the seller on the market, lowered by the sold amount
+    #[account(
+        mut,
+        seeds = [WALLET_POSITION_PDA_SEED.as_bytes(), market.key().as_ref(), seller.key().as_ref()],
+        bump = wallet_position.bump,
+    )]
+    pub wallet_position: Option<Account<'info, WalletPosition>>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Sells base tokens back to a SOL-quoted market. The tokens are burned (mirroring the mint on
+/// `buy`) and the seller is paid along the bid curve from the lamports held by the market PDA.
+/// As with `swap`, fees are taken out of the ask/bid spread paid on buys, so the bid curve is
+/// always backed and sells carry no fee: the creator, protocol and referral split of a round
+/// trip is charged once, on its buy.
+///
+/// The market PDA is owned by this program and holds data, so lamports are moved out of it
+/// directly instead of through the system program.
+pub fn handler(
+    ctx: Context<Sell>,
+    swap_amount_type: SwapAmountType, // ExactInput = base tokens, ExactOutput = quote lamports
+    amount: u64,
+    other_amount_threshold: u64, // min lamports out (ExactInput) or max base tokens in (ExactOutput)
+    deadline: Option<Deadline>,
+) -> Result<(u64, u64)> {
+    assert_deadline(deadline)?;
+
+    let (base_amount, quote_amount, pending_fee_lamports) = {
+        let mut market = ctx.accounts.market.load_mut()?;
+
+        if market.is_migrated != 0 {
+            return Err(error!(TokenMillError::MarketMigrated));
+        }
+        market.assert_not_paused(&ctx.accounts.config)?;
+
+        // Locked markets only trade through `permissioned_swap`
+        if market.is_permissioned != 0 {
+            return Err(error!(TokenMillError::UnauthorizedMarket));
+        }
+
+        let (base_amount, quote_amount, _) =
+            curve::get_swap_amounts(&market, SwapType::Sell, swap_amount_type, amount)?;
+
+        let threshold_met = match swap_amount_type {
+            SwapAmountType::ExactInput => quote_amount >= other_amount_threshold,
+            SwapAmountType::ExactOutput => base_amount <= other_amount_threshold,
+        };
+        if !threshold_met {
+            return Err(error!(TokenMillError::AmountThresholdNotMet));
+        }
+
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+        if let Some(trader_stats) = &mut ctx.accounts.trader_stats {
+            trader_stats.record_volume(quote_amount, now)?;
+        }
+
+        market.record_position_trade(
+            SwapType::Sell,
+            base_amount,
+            ctx.accounts.wallet_position.as_deref_mut(),
+            now,
+        )?;
+
+        // Any trade starts the decay of the launch fee, which only buys pay
+        market.launch_fee.start(&clock);
+
+        // Sold tokens are burned, so they leave the circulating supply without refilling the reserve
+        market.decrease_circulating_supply(base_amount)?;
+
+        (base_amount, quote_amount, market.fee_vault.pending_lamports()?)
+    };
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.base_token_mint.to_account_info(),
+                from: ctx.accounts.seller_base_token_ata.to_account_info(),
+                authority: ctx.accounts.seller.to_account_info(),
+            },
+        ),
+        base_amount,
+    )?;
+
+    // The market must stay rent exempt and keep backing its fee vault after paying the seller
+    let market_info = ctx.accounts.market.to_account_info();
+    let min_balance = Rent::get()?.minimum_balance(market_info.data_len());
+    let available = market_info
+        .lamports()
+        .saturating_sub(min_balance)
+        .saturating_sub(pending_fee_lamports);
+    if quote_amount > available {
+        return Err(error!(TokenMillError::InvalidMarketState));
+    }
+
+    pay_from_market(
+        &market_info,
+        &ctx.accounts.seller.to_account_info(),
+        quote_amount,
+    )?;
+
+    emit_cpi!(TokenMillSwapEvent {
+        user: ctx.accounts.seller.key(),
+        market: ctx.accounts.market.key(),
+        swap_type: SwapType::Sell,
+        base_amount,
+        quote_amount,
+        referral_token_account: None,
+        creator_fee: 0,
+        staking_fee: 0,
+        protocol_fee: 0,
+        referral_fee: 0,
+        launch_fee: 0,
+    });
+
+    Ok((base_amount, quote_amount))
+}
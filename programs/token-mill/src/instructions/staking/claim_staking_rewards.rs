@@ -4,13 +4,19 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 use crate::{
     errors::TokenMillError,
     events::TokenMillStakingRewardsClaimEvent,
-    state::{Market, MarketStaking, StakePosition},
+    state::{Market, MarketStaking, StakePosition, TokenMillConfig},
 };
 
 #[event_cpi]
 #[derive(Accounts)]
 pub struct StakingRewardsClaim<'info> {
-    #[account(mut, has_one = quote_token_mint @ TokenMillError::InvalidMintAccount)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+    )]
     pub market: AccountLoader<'info, Market>,
 
     #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
@@ -47,6 +53,11 @@ pub struct StakingRewardsClaim<'info> {
 }
 
 pub fn handler(ctx: Context<StakingRewardsClaim>) -> Result<u64> {
+    ctx.accounts
+        .market
+        .load()?
+        .assert_not_paused(&ctx.accounts.config)?;
+
     let pending_rewards = 0;
 
     emit_cpi!(TokenMillStakingRewardsClaimEvent {
@@ -1,7 +1,7 @@
 use crate::{
     errors::TokenMillError,
     events::TokenMillStakingDepositEvent,
-    state::{Market, MarketStaking, StakePosition},
+    state::{Market, MarketStaking, StakePosition, TokenMillConfig},
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -9,7 +9,13 @@ use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 #[event_cpi]
 #[derive(Accounts)]
 pub struct StakeUpdate<'info> {
-    #[account(mut, has_one = base_token_mint @ TokenMillError::InvalidMintAccount)]
+    pub config: Account<'info, TokenMillConfig>,
+
+    #[account(
+        mut,
+        has_one = config @ TokenMillError::InvalidConfigAccount,
+        has_one = base_token_mint @ TokenMillError::InvalidMintAccount
+    )]
     pub market: AccountLoader<'info, Market>,
 
     #[account(mut, has_one = market @ TokenMillError::InvalidMarket)]
@@ -46,6 +52,11 @@ pub struct StakeUpdate<'info> {
 }
 
 pub fn handler(ctx: Context<StakeUpdate>, amount: u64) -> Result<()> {
+    ctx.accounts
+        .market
+        .load()?
+        .assert_not_paused(&ctx.accounts.config)?;
+
     emit_cpi!(TokenMillStakingDepositEvent {
         market: ctx.accounts.market.key(),
         user: ctx.accounts.user.key(),
@@ -1,9 +1,20 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 
 use crate::{
-    errors::TokenMillError, events::TokenMillSwapEvent, state::Market, SwapAmountType, SwapType,
-    TokenMillConfig,
+    constant::BPS_DENOMINATOR,
+    curve,
+    discount::{compute_trader_discount_bp, DiscountAccounts},
+    errors::TokenMillError,
+    events::TokenMillSwapEvent,
+    state::{
+        DiscountSchedule, Market, StakePosition, TraderStats, WalletPosition,
+        DISCOUNT_SCHEDULE_PDA_SEED, MARKET_PDA_SEED, TRADER_STATS_PDA_SEED,
+        WALLET_POSITION_PDA_SEED,
+    },
+    token_extensions::{get_amount_after_fee, get_amount_before_fee},
+    SwapAmountType, SwapType, TokenMillConfig,
 };
 
 #[event_cpi]
@@ -15,7 +26,9 @@ pub struct Swap<'info> {
         mut,
         has_one = config @ TokenMillError::InvalidConfigAccount,
         has_one = base_token_mint @ TokenMillError::InvalidMintAccount,
-        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount
+        has_one = quote_token_mint @ TokenMillError::InvalidMintAccount,
+        // Lamport markets hold no quote reserve and trade through `buy` and `sell`
+        constraint = market.load()?.quote_token_mint != native_mint::ID @ TokenMillError::InvalidQuoteTokenMint
     )]
     pub market: AccountLoader<'info, Market>,
 
@@ -60,30 +73,609 @@ pub struct Swap<'info> {
 
     pub user: Signer<'info>,
 
+    /// Optional rolling volume of the user, read for volume-based fee discounts. Swaps are quoted
+    /// in SPL tokens and don't add to it
+    #[account(
+        seeds = [TRADER_STATS_PDA_SEED.as_bytes(), config.key().as_ref(), user.key().as_ref()],
+        bump = trader_stats.bump,
+    )]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+
+    /// Optional discount tiers of the config, without them no discount applies
+    #[account(
+        seeds = [DISCOUNT_SCHEDULE_PDA_SEED.as_bytes(), config.key().as_ref()],
+        bump = discount_schedule.bump,
+    )]
+    pub discount_schedule: Option<Account<'info, DiscountSchedule>>,
+
+    /// Optional token account of the user holding the mint of a `TokenHolding` discount strategy
+    pub discount_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Optional stake position of the user on the market of a `StakedBalance` discount strategy
+    pub stake_position: Option<Account<'info, StakePosition>>,
+
+    /// Optional position of the user on the market, required before the market opens and on buys
+    /// when the market caps wallets
+    #[account(
+        mut,
+        seeds = [WALLET_POSITION_PDA_SEED.as_bytes(), market.key().as_ref(), user.key().as_ref()],
+        bump = wallet_position.bump,
+    )]
+    pub wallet_position: Option<Account<'info, WalletPosition>>,
+
     pub base_token_program: Interface<'info, TokenInterface>,
 
     pub quote_token_program: Interface<'info, TokenInterface>,
 }
 
+/// Fee components of a swap, all denominated in quote tokens.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SwapFees {
+    pub creator_fee: u64,
+    pub staking_fee: u64,
+    pub protocol_fee: u64,
+    pub referral_fee: u64,
+    /// Part of the fee waived by the trader's discount or the swap authority's fee override,
+    /// never charged
+    pub discount: u64,
+    /// Anti-sniper fee charged on top of a buy during the launch phase
+    pub launch_fee: u64,
+}
+
+/// Amounts of a swap on both ends of its token transfers. `base_amount` and `quote_amount` are
+/// sent or received by the user, the `market_*` amounts are received or sent by the market and
+/// priced on the curve. They only differ for mints charging Token-2022 transfer fees.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SwapAmounts {
+    pub base_amount: u64,
+    pub quote_amount: u64,
+    pub market_base_amount: u64,
+    pub market_quote_amount: u64,
+    /// Ask/bid spread paid on buys, out of `market_quote_amount`
+    pub fee: u64,
+}
+
+/// Accounts needed to settle a swap, shared by `swap` and `permissioned_swap`.
+pub struct SwapAccounts<'a, 'info> {
+    pub config: &'a Account<'info, TokenMillConfig>,
+    pub market: &'a AccountLoader<'info, Market>,
+    pub base_token_mint: &'a InterfaceAccount<'info, Mint>,
+    pub quote_token_mint: &'a InterfaceAccount<'info, Mint>,
+    pub market_base_token_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub market_quote_token_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub user_base_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub user_quote_token_account: &'a InterfaceAccount<'info, TokenAccount>,
+    pub protocol_quote_token_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub referral_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    /// Receives the output of the swap instead of the user's token account when set
+    pub recipient_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub user: &'a Signer<'info>,
+    pub base_token_program: &'a Interface<'info, TokenInterface>,
+    pub quote_token_program: &'a Interface<'info, TokenInterface>,
+}
+
+impl<'info> Swap<'info> {
+    pub fn swap_accounts(&self) -> SwapAccounts<'_, 'info> {
+        SwapAccounts {
+            config: &self.config,
+            market: &self.market,
+            base_token_mint: &self.base_token_mint,
+            quote_token_mint: &self.quote_token_mint,
+            market_base_token_ata: &self.market_base_token_ata,
+            market_quote_token_ata: &self.market_quote_token_ata,
+            user_base_token_account: &self.user_base_token_account,
+            user_quote_token_account: &self.user_quote_token_account,
+            protocol_quote_token_ata: &self.protocol_quote_token_ata,
+            referral_token_account: self.referral_token_account.as_ref(),
+            recipient_token_account: None,
+            user: &self.user,
+            base_token_program: &self.base_token_program,
+            quote_token_program: &self.quote_token_program,
+        }
+    }
+}
+
 pub fn handler(
     ctx: Context<Swap>,
     swap_type: SwapType,
-    _swap_amount_type: SwapAmountType,
-    _amount: u64,
-    _other_amount_threshold: u64,
+    swap_amount_type: SwapAmountType,
+    amount: u64,
+    other_amount_threshold: u64,
 ) -> Result<(u64, u64)> {
+    // Locked markets only trade through `permissioned_swap`
+    if ctx.accounts.market.load()?.is_permissioned != 0 {
+        return Err(error!(TokenMillError::UnauthorizedMarket));
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let discount_bp = compute_trader_discount_bp(
+        &ctx.accounts.config,
+        &ctx.accounts.user.key(),
+        &DiscountAccounts {
+            discount_schedule: ctx.accounts.discount_schedule.as_deref(),
+            trader_stats: ctx.accounts.trader_stats.as_deref(),
+            token_account: ctx.accounts.discount_token_account.as_ref(),
+            stake_position: ctx.accounts.stake_position.as_deref(),
+        },
+        now,
+    )?;
+
+    let (base_amount, quote_amount, fees) = execute_swap(
+        &ctx.accounts.swap_accounts(),
+        swap_type,
+        swap_amount_type,
+        amount,
+        other_amount_threshold,
+        discount_bp,
+        None,
+    )?;
+
+    ctx.accounts.market.load()?.record_position_trade(
+        swap_type,
+        base_amount,
+        ctx.accounts.wallet_position.as_deref_mut(),
+        now,
+    )?;
+
     emit_cpi!(TokenMillSwapEvent {
         user: ctx.accounts.user.key(),
         market: ctx.accounts.market.key(),
-        swap_type: swap_type,
-        base_amount: 0,
-        quote_amount: 0,
-        referral_token_account: None,
-        creator_fee: 0,
-        staking_fee: 0,
-        protocol_fee: 0,
-        referral_fee: 0,
+        swap_type,
+        base_amount,
+        quote_amount,
+        referral_token_account: ctx
+            .accounts
+            .referral_token_account
+            .as_ref()
+            .map(|a| a.key()),
+        creator_fee: fees.creator_fee,
+        staking_fee: fees.staking_fee,
+        protocol_fee: fees.protocol_fee,
+        referral_fee: fees.referral_fee,
+        launch_fee: fees.launch_fee,
     });
 
-    Ok((0, 0))
+    Ok((base_amount, quote_amount))
+}
+
+/// Executes a swap against the market's price curves.
+///
+/// Buys pay the ask curve and sells receive the bid curve. The spread between the two curves
+/// on a buy is the swap fee: it is split between the creator, stakers and the protocol (with
+/// the referrer taking a share of the protocol part). `discount_bp` waives part of the protocol
+/// fee, which the buyer doesn't pay, while the market's launch fee is added on top of buys
+/// during the launch phase. The whole payment is sent to
+/// `market_quote_token_ata`, which forwards the protocol and referral fees. Creator and
+/// staking fees stay there and are tracked as pending on the market.
+pub fn execute_swap(
+    accounts: &SwapAccounts,
+    swap_type: SwapType,
+    swap_amount_type: SwapAmountType,
+    amount: u64,
+    other_amount_threshold: u64,
+    discount_bp: u128,
+    fee_override_bps: Option<u16>,
+) -> Result<(u64, u64, SwapFees)> {
+    if amount == 0 {
+        return Err(error!(TokenMillError::InvalidAmount));
+    }
+
+    let (user_output_token_account, output_mint) = match swap_type {
+        SwapType::Buy => (accounts.user_base_token_account, accounts.base_token_mint),
+        SwapType::Sell => (accounts.user_quote_token_account, accounts.quote_token_mint),
+    };
+    let output_token_account = match accounts.recipient_token_account {
+        Some(recipient_token_account) => {
+            if recipient_token_account.mint != output_mint.key() {
+                return Err(error!(TokenMillError::InvalidMintAccount));
+            }
+            recipient_token_account
+        }
+        None => user_output_token_account,
+    };
+
+    if let Some(referral_token_account) = accounts.referral_token_account {
+        if referral_token_account.mint != accounts.quote_token_mint.key() {
+            return Err(error!(TokenMillError::InvalidReferralAccount));
+        }
+    }
+    let has_referral = accounts.referral_token_account.is_some();
+
+    // Compute amounts and update the market state before any CPI, as the market PDA signs the
+    // outgoing transfers and must not be borrowed at that point.
+    let (amounts, fees, bump) = {
+        let mut market = accounts.market.load_mut()?;
+
+        if market.is_migrated != 0 {
+            return Err(error!(TokenMillError::MarketMigrated));
+        }
+        market.assert_not_paused(accounts.config)?;
+
+        let clock = Clock::get()?;
+        let launch_fee_bps = launch_fee_bps(&market, swap_type, &clock);
+
+        let mut amounts = compute_swap_amounts(
+            &market,
+            accounts.base_token_mint,
+            accounts.quote_token_mint,
+            swap_type,
+            swap_amount_type,
+            amount_before_launch_fee(amount, swap_type, swap_amount_type, launch_fee_bps),
+        )?;
+        let mut fees = match fee_override_bps {
+            Some(fee_override_bps) => compute_overridden_fees(
+                &market,
+                accounts.config,
+                amounts.fee,
+                has_referral,
+                fee_override_bps,
+            )?,
+            None => compute_fees(
+                &market,
+                accounts.config,
+                amounts.fee,
+                has_referral,
+                discount_bp,
+            )?,
+        };
+        apply_fee_discount(&mut amounts, &fees, accounts.quote_token_mint)?;
+        apply_launch_fee(
+            &mut amounts,
+            &mut fees,
+            launch_fee_bps,
+            accounts.quote_token_mint,
+        )?;
+
+        let (amount_in, amount_out) = match swap_type {
+            SwapType::Buy => (amounts.quote_amount, amounts.base_amount),
+            SwapType::Sell => (amounts.base_amount, amounts.quote_amount),
+        };
+        match swap_amount_type {
+            SwapAmountType::ExactInput => {
+                if amount_out < other_amount_threshold {
+                    return Err(error!(TokenMillError::AmountThresholdNotMet));
+                }
+            }
+            SwapAmountType::ExactOutput => {
+                if amount_in > other_amount_threshold {
+                    return Err(error!(TokenMillError::AmountThresholdNotMet));
+                }
+            }
+        }
+
+        match swap_type {
+            SwapType::Buy => {
+                market.base_reserve = market
+                    .base_reserve
+                    .checked_sub(amounts.market_base_amount)
+                    .ok_or(error!(TokenMillError::InvalidAmount))?;
+                market.increase_circulating_supply(amounts.market_base_amount)?;
+            }
+            SwapType::Sell => {
+                market.base_reserve = market
+                    .base_reserve
+                    .checked_add(amounts.market_base_amount)
+                    .ok_or(error!(TokenMillError::MathOverflow))?;
+                market.decrease_circulating_supply(amounts.market_base_amount)?;
+            }
+        }
+
+        market.launch_fee.start(&clock);
+        market.accrue_launch_fee(fees.launch_fee, false)?;
+
+        market.fees.pending_creator_fees = market
+            .fees
+            .pending_creator_fees
+            .checked_add(fees.creator_fee)
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+        market.fees.pending_staking_fees = market
+            .fees
+            .pending_staking_fees
+            .checked_add(fees.staking_fee)
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+
+        (amounts, fees, market.bump)
+    };
+
+    let base_token_mint_key = accounts.base_token_mint.key();
+    let seeds: &[&[u8]] = &[
+        MARKET_PDA_SEED.as_bytes(),
+        base_token_mint_key.as_ref(),
+        &[bump],
+    ];
+    let signer_seeds: &[&[&[u8]]] = &[seeds];
+
+    match swap_type {
+        SwapType::Buy => {
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    accounts.quote_token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.user_quote_token_account.to_account_info(),
+                        mint: accounts.quote_token_mint.to_account_info(),
+                        to: accounts.market_quote_token_ata.to_account_info(),
+                        authority: accounts.user.to_account_info(),
+                    },
+                ),
+                amounts.quote_amount,
+                accounts.quote_token_mint.decimals,
+            )?;
+
+            transfer_quote_from_market(
+                accounts,
+                accounts.protocol_quote_token_ata.to_account_info(),
+                fees.protocol_fee,
+                signer_seeds,
+            )?;
+            if let Some(referral_token_account) = accounts.referral_token_account {
+                transfer_quote_from_market(
+                    accounts,
+                    referral_token_account.to_account_info(),
+                    fees.referral_fee,
+                    signer_seeds,
+                )?;
+            }
+
+            token_interface::transfer_checked(
+                CpiContext::new_with_signer(
+                    accounts.base_token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.market_base_token_ata.to_account_info(),
+                        mint: accounts.base_token_mint.to_account_info(),
+                        to: output_token_account.to_account_info(),
+                        authority: accounts.market.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                amounts.market_base_amount,
+                accounts.base_token_mint.decimals,
+            )?;
+        }
+        SwapType::Sell => {
+            token_interface::transfer_checked(
+                CpiContext::new(
+                    accounts.base_token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.user_base_token_account.to_account_info(),
+                        mint: accounts.base_token_mint.to_account_info(),
+                        to: accounts.market_base_token_ata.to_account_info(),
+                        authority: accounts.user.to_account_info(),
+                    },
+                ),
+                amounts.base_amount,
+                accounts.base_token_mint.decimals,
+            )?;
+
+            transfer_quote_from_market(
+                accounts,
+                output_token_account.to_account_info(),
+                amounts.market_quote_amount,
+                signer_seeds,
+            )?;
+        }
+    }
+
+    Ok((amounts.base_amount, amounts.quote_amount, fees))
+}
+
+/// Prices a swap on the curve, accounting for the transfer fees charged on each leg so the
+/// curve only sees what the market actually receives or sends.
+pub fn compute_swap_amounts(
+    market: &Market,
+    base_token_mint: &InterfaceAccount<Mint>,
+    quote_token_mint: &InterfaceAccount<Mint>,
+    swap_type: SwapType,
+    swap_amount_type: SwapAmountType,
+    amount: u64,
+) -> Result<SwapAmounts> {
+    let market_amount = match (swap_type, swap_amount_type) {
+        (SwapType::Buy, SwapAmountType::ExactInput) => {
+            get_amount_after_fee(quote_token_mint, amount)?
+        }
+        (SwapType::Buy, SwapAmountType::ExactOutput) => {
+            get_amount_before_fee(base_token_mint, amount)?
+        }
+        (SwapType::Sell, SwapAmountType::ExactInput) => {
+            get_amount_after_fee(base_token_mint, amount)?
+        }
+        (SwapType::Sell, SwapAmountType::ExactOutput) => {
+            get_amount_before_fee(quote_token_mint, amount)?
+        }
+    };
+
+    let (market_base_amount, market_quote_amount, fee) =
+        curve::get_swap_amounts(market, swap_type, swap_amount_type, market_amount)?;
+
+    let (base_amount, quote_amount) = match swap_type {
+        SwapType::Buy => (
+            get_amount_after_fee(base_token_mint, market_base_amount)?,
+            get_amount_before_fee(quote_token_mint, market_quote_amount)?,
+        ),
+        SwapType::Sell => {
+            let base_amount = match swap_amount_type {
+                SwapAmountType::ExactInput => amount,
+                SwapAmountType::ExactOutput => {
+                    get_amount_before_fee(base_token_mint, market_base_amount)?
+                }
+            };
+            (
+                base_amount,
+                get_amount_after_fee(quote_token_mint, market_quote_amount)?,
+            )
+        }
+    };
+
+    Ok(SwapAmounts {
+        base_amount,
+        quote_amount,
+        market_base_amount,
+        market_quote_amount,
+        fee,
+    })
+}
+
+fn transfer_quote_from_market<'info>(
+    accounts: &SwapAccounts<'_, 'info>,
+    to: AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.quote_token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.market_quote_token_ata.to_account_info(),
+                mint: accounts.quote_token_mint.to_account_info(),
+                to,
+                authority: accounts.market.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        accounts.quote_token_mint.decimals,
+    )
+}
+
+/// Splits a swap fee between the creator, stakers, the protocol and the referrer.
+pub fn compute_fees(
+    market: &Market,
+    config: &TokenMillConfig,
+    fee: u64,
+    has_referral: bool,
+    discount_bp: u128,
+) -> Result<SwapFees> {
+    let bps = |amount: u64, share: u16| -> Result<u64> {
+        let value = (amount as u128)
+            .checked_mul(share as u128)
+            .ok_or(error!(TokenMillError::MathOverflow))?
+            / BPS_DENOMINATOR as u128;
+        Ok(value as u64)
+    };
+
+    let (creator_fee_share, staking_fee_share) = market.fee_shares_at(Clock::get()?.unix_timestamp);
+    let creator_fee = bps(fee, creator_fee_share)?;
+    let staking_fee = bps(fee, staking_fee_share)?;
+    let protocol_fee_total = fee
+        .checked_sub(creator_fee)
+        .and_then(|f| f.checked_sub(staking_fee))
+        .ok_or(error!(TokenMillError::InvalidFeeShare))?;
+    let discount = (protocol_fee_total as u128)
+        .checked_mul(discount_bp)
+        .ok_or(error!(TokenMillError::MathOverflow))?
+        / BPS_DENOMINATOR as u128;
+    let protocol_fee_total = protocol_fee_total - discount as u64;
+
+    let referral_fee = if has_referral {
+        bps(protocol_fee_total, config.referral_fee_share)?
+    } else {
+        0
+    };
+
+    Ok(SwapFees {
+        creator_fee,
+        staking_fee,
+        protocol_fee: protocol_fee_total - referral_fee,
+        referral_fee,
+        discount: discount as u64,
+        launch_fee: 0,
+    })
+}
+
+/// Fee split of a swap whose fee is set by the swap authority of a permissioned market. Only
+/// `fee_override_bps` of the creator and staking fees is charged, the rest is waived like a
+/// discount. The protocol and referral fees are always charged in full.
+pub fn compute_overridden_fees(
+    market: &Market,
+    config: &TokenMillConfig,
+    fee: u64,
+    has_referral: bool,
+    fee_override_bps: u16,
+) -> Result<SwapFees> {
+    if fee_override_bps > BPS_DENOMINATOR {
+        return Err(error!(TokenMillError::InvalidFeeShare));
+    }
+
+    let overridden = |amount: u64| -> u64 {
+        (amount as u128 * fee_override_bps as u128 / BPS_DENOMINATOR as u128) as u64
+    };
+
+    let mut fees = compute_fees(market, config, fee, has_referral, 0)?;
+    let creator_fee = overridden(fees.creator_fee);
+    let staking_fee = overridden(fees.staking_fee);
+    fees.discount = (fees.creator_fee - creator_fee) + (fees.staking_fee - staking_fee);
+    fees.creator_fee = creator_fee;
+    fees.staking_fee = staking_fee;
+
+    Ok(fees)
+}
+
+/// Launch fee in effect for a trade, in basis points. Only buys pay it.
+pub fn launch_fee_bps(market: &Market, swap_type: SwapType, clock: &Clock) -> u16 {
+    match swap_type {
+        SwapType::Buy => market.launch_fee.fee_bps(clock),
+        SwapType::Sell => 0,
+    }
+}
+
+/// Part of an exact-input buy budget left for the trade once its launch fee is set aside.
+pub fn amount_before_launch_fee(
+    amount: u64,
+    swap_type: SwapType,
+    swap_amount_type: SwapAmountType,
+    launch_fee_bps: u16,
+) -> u64 {
+    match (swap_type, swap_amount_type) {
+        (SwapType::Buy, SwapAmountType::ExactInput) => {
+            (amount as u128 * BPS_DENOMINATOR as u128
+                / (BPS_DENOMINATOR as u128 + launch_fee_bps as u128)) as u64
+        }
+        _ => amount,
+    }
+}
+
+/// Launch fee charged on top of `quote_amount`, rounded down.
+pub fn compute_launch_fee(quote_amount: u64, launch_fee_bps: u16) -> u64 {
+    (quote_amount as u128 * launch_fee_bps as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// Adds the launch fee to the payment of the buyer.
+pub fn apply_launch_fee(
+    amounts: &mut SwapAmounts,
+    fees: &mut SwapFees,
+    launch_fee_bps: u16,
+    quote_token_mint: &InterfaceAccount<Mint>,
+) -> Result<()> {
+    fees.launch_fee = compute_launch_fee(amounts.market_quote_amount, launch_fee_bps);
+    if fees.launch_fee == 0 {
+        return Ok(());
+    }
+
+    amounts.market_quote_amount = amounts
+        .market_quote_amount
+        .checked_add(fees.launch_fee)
+        .ok_or(error!(TokenMillError::MathOverflow))?;
+    amounts.quote_amount = get_amount_before_fee(quote_token_mint, amounts.market_quote_amount)?;
+
+    Ok(())
+}
+
+/// Takes the discount waived on a buy's fee off the payment of the buyer.
+pub fn apply_fee_discount(
+    amounts: &mut SwapAmounts,
+    fees: &SwapFees,
+    quote_token_mint: &InterfaceAccount<Mint>,
+) -> Result<()> {
+    if fees.discount == 0 {
+        return Ok(());
+    }
+
+    amounts.fee -= fees.discount;
+    amounts.market_quote_amount -= fees.discount;
+    amounts.quote_amount = get_amount_before_fee(quote_token_mint, amounts.market_quote_amount)?;
+
+    Ok(())
 }
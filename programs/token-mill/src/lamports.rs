@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::TokenMillError;
+
+/// Moves `amount` lamports out of an account owned by this program, such as a market or a
+/// referral PDA. Those accounts hold data, so the system program can't debit them.
+pub fn pay_from_market<'info>(
+    market: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let mut market_lamports = market.try_borrow_mut_lamports()?;
+    **market_lamports = market_lamports
+        .checked_sub(amount)
+        .ok_or(error!(TokenMillError::InvalidMarketState))?;
+
+    let mut to_lamports = to.try_borrow_mut_lamports()?;
+    **to_lamports = to_lamports
+        .checked_add(amount)
+        .ok_or(error!(TokenMillError::MathOverflow))?;
+
+    Ok(())
+}
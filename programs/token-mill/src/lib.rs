@@ -5,6 +5,7 @@ use anchor_lang::prelude::*;
 declare_id!("3AaPEKEdJm6k8goX5kjpL9xuRPC6c8a1SuoQvaPi9hXt");
 
 pub mod constant;
+pub mod curve;
 pub mod errors;
 mod events;
 mod instructions;
@@ -18,6 +19,8 @@ mod merkle;
 pub mod migration;
 pub mod authority;
 pub mod security;
+pub mod lamports;
+pub mod token_extensions;
 
 use instructions::*;
 use state::*;
@@ -34,6 +37,39 @@ pub enum SwapAmountType {
     ExactOutput,
 }
 
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq)]
+pub enum Deadline {
+    UnixTimestamp(i64), // Last unix timestamp at which the trade can execute
+    Slot(u64),          // Last slot at which the trade can execute
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq)]
+pub enum CurveParams {
+    PriceArray, // Bid and ask price points, set later with `set_market_prices`
+    Exponential {
+        initial_price: u64, // Quote units per whole base token at zero supply
+        final_price: u64,   // Quote units per whole base token at the total supply
+        spread_bps: u16,    // Ask premium over the bid curve
+    },
+    ConstantProduct {
+        virtual_base_reserve: u64, // Must exceed the total supply
+        virtual_quote_reserve: u64,
+        spread_bps: u16, // Ask premium over the bid curve
+    },
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq)]
+pub enum LaunchFeeDecay {
+    Slots(u64),   // Slots after the first trade over which the launch fee decays to 0
+    Seconds(u64), // Seconds after the first trade over which the launch fee decays to 0
+}
+
+#[derive(Debug, AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq)]
+pub enum LaunchFeeRecipient {
+    Creator, // Accrued with the creator fees
+    Buyback, // Accrued to the market's buyback pool
+}
+
 #[program]
 pub mod token_mill {
     use super::*;
@@ -62,6 +98,12 @@ pub mod token_mill {
         total_supply: u64,
         creator_fee_share: u16,
         staking_fee_share: u16,
+        curve: CurveParams,
+        max_base_per_transaction: u64,
+        max_base_per_wallet: u64,
+        open_at: i64,
+        presale_root: Option<[u8; 32]>,
+        presale_max_base_per_wallet: u64,
     ) -> Result<()> {
         instructions::create_market::handler(
             ctx,
@@ -71,6 +113,12 @@ pub mod token_mill {
             total_supply,
             creator_fee_share,
             staking_fee_share,
+            curve,
+            max_base_per_transaction,
+            max_base_per_wallet,
+            open_at,
+            presale_root,
+            presale_max_base_per_wallet,
         )
     }
 
@@ -82,6 +130,12 @@ pub mod token_mill {
         total_supply: u64,
         creator_fee_share: u16,
         staking_fee_share: u16,
+        curve: CurveParams,
+        max_base_per_transaction: u64,
+        max_base_per_wallet: u64,
+        open_at: i64,
+        presale_root: Option<[u8; 32]>,
+        presale_max_base_per_wallet: u64,
     ) -> Result<()> {
         instructions::create_market_with_spl::handler(
             ctx,
@@ -91,9 +145,40 @@ pub mod token_mill {
             total_supply,
             creator_fee_share,
             staking_fee_share,
+            curve,
+            max_base_per_transaction,
+            max_base_per_wallet,
+            open_at,
+            presale_root,
+            presale_max_base_per_wallet,
         )
     }
 
+    pub fn create_trader_stats(ctx: Context<CreateTraderStats>) -> Result<()> {
+        instructions::create_trader_stats::handler(ctx)
+    }
+
+    pub fn create_wallet_position(ctx: Context<CreateWalletPosition>) -> Result<()> {
+        instructions::create_wallet_position::handler(ctx)
+    }
+
+    pub fn migrate_market_layout(ctx: Context<MigrateMarketLayout>) -> Result<()> {
+        instructions::migrate_market_layout::handler(ctx)
+    }
+
+    pub fn join_presale(ctx: Context<JoinPresale>, index: u64, proof: Vec<[u8; 32]>) -> Result<()> {
+        instructions::join_presale::handler(ctx, index, proof)
+    }
+
+    pub fn set_launch_fee(
+        ctx: Context<MarketSettingsUpdate>,
+        start_fee_bps: u16,
+        decay: LaunchFeeDecay,
+        recipient: LaunchFeeRecipient,
+    ) -> Result<()> {
+        instructions::set_launch_fee::handler(ctx, start_fee_bps, decay, recipient)
+    }
+
     pub fn set_market_prices(
         ctx: Context<MarketSettingsUpdate>,
         bid_prices: [u64; constant::PRICES_LENGTH],
@@ -118,12 +203,49 @@ pub mod token_mill {
         )
     }
 
+    /// Prices a trade with the same curve and fee split as `swap`, `buy` and `sell` without
+    /// mutating any account. The quote is returned as return data so clients can simulate it.
+    pub fn quote_swap(
+        ctx: Context<QuoteSwap>,
+        swap_type: SwapType,
+        swap_amount_type: SwapAmountType,
+        amount: u64,
+    ) -> Result<SwapQuote> {
+        instructions::quote_swap::handler(ctx, swap_type, swap_amount_type, amount)
+    }
+
+    /// Buy using SOL (lamports) as quote asset. This enforces payment to the market PDA before minting.
     pub fn buy(
         ctx: Context<Purchase>,
-        swap_amount_type: u8,
+        swap_amount_type: SwapAmountType,
+        amount: u64,
+        other_amount_threshold: u64,
+        deadline: Option<Deadline>,
+    ) -> Result<(u64, u64)> {
+        instructions::purchase::handler(
+            ctx,
+            swap_amount_type,
+            amount,
+            other_amount_threshold,
+            deadline,
+        )
+    }
+
+    /// Sell base tokens back to a SOL-quoted market, paid in lamports from the market PDA.
+    pub fn sell(
+        ctx: Context<Sell>,
+        swap_amount_type: SwapAmountType,
         amount: u64,
+        other_amount_threshold: u64,
+        deadline: Option<Deadline>,
     ) -> Result<(u64, u64)> {
-        instructions::purchase::handler(ctx, swap_amount_type, amount)
+        instructions::sell::handler(
+            ctx,
+            swap_amount_type,
+            amount,
+            other_amount_threshold,
+            deadline,
+        )
     }
 
     pub fn perform_buyback(ctx: Context<crate::buyback::PerformBuyback>, lamports: u64) -> Result<()> {
@@ -150,11 +272,6 @@ pub mod token_mill {
         crate::airdrop::claim_handler(ctx, index, leaf, proof)
     }
 
-    /// Buy using SOL (lamports) as quote asset. This enforces payment to the market PDA before minting.
-    pub fn buy(ctx: Context<instructions::purchase::Purchase>, swap_amount_type: u8, amount: u64) -> Result<(u64, u64)> {
-        instructions::purchase::handler(ctx, swap_amount_type, amount)
-    }
-
     // Staking
     pub fn create_staking(ctx: Context<CreateStaking>) -> Result<()> {
         instructions::staking::create_staking::handler(ctx)
@@ -198,8 +315,27 @@ pub mod token_mill {
     }
 
     // Permissioned markets
-    pub fn lock_market(ctx: Context<LockMarket>, authority: Pubkey) -> Result<()> {
-        instructions::permissioned_markets::lock_market::handler(ctx, authority)
+    pub fn lock_market(
+        ctx: Context<LockMarket>,
+        authority: Pubkey,
+        scope: SwapAuthorityScope,
+    ) -> Result<()> {
+        instructions::permissioned_markets::lock_market::handler(ctx, authority, scope)
+    }
+
+    pub fn grant_swap_authority(
+        ctx: Context<GrantSwapAuthority>,
+        authority: Pubkey,
+        scope: SwapAuthorityScope,
+    ) -> Result<()> {
+        instructions::permissioned_markets::grant_swap_authority::handler(ctx, authority, scope)
+    }
+
+    pub fn revoke_swap_authority(
+        ctx: Context<RevokeSwapAuthority>,
+        authority: Pubkey,
+    ) -> Result<()> {
+        instructions::permissioned_markets::revoke_swap_authority::handler(ctx, authority)
     }
 
     pub fn permissioned_swap(
@@ -208,6 +344,7 @@ pub mod token_mill {
         swap_amount_type: SwapAmountType,
         amount: u64,
         other_amount_threshold: u64,
+        fee_override_bps: Option<u16>,
     ) -> Result<(u64, u64)> {
         instructions::permissioned_markets::permissioned_swap::handler(
             ctx,
@@ -215,11 +352,12 @@ pub mod token_mill {
             swap_amount_type,
             amount,
             other_amount_threshold,
+            fee_override_bps,
         )
     }
 
-    pub fn free_market(ctx: Context<FreeMarket>) -> Result<()> {
-        instructions::permissioned_markets::free_market::handler(ctx)
+    pub fn free_market(ctx: Context<FreeMarket>, lock_authority: Pubkey) -> Result<()> {
+        instructions::permissioned_markets::free_market::handler(ctx, lock_authority)
     }
 
     // Referrals
@@ -240,7 +378,7 @@ pub mod token_mill {
     }
 
     pub fn update_market_fee_shares(
-        ctx: Context<MarketSettingsUpdate>,
+        ctx: Context<MarketFeeSharesUpdate>,
         new_creator_fee_share: u16,
         new_staking_fee_share: u16,
     ) -> Result<()> {
@@ -251,10 +389,44 @@ pub mod token_mill {
         )
     }
 
-    pub fn claim_creator_fees(ctx: Context<ClaimCreatorFees>) -> Result<()> {
+    pub fn claim_creator_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimCreatorFees<'info>>,
+    ) -> Result<()> {
         instructions::claim_creator_fees::handler(ctx)
     }
 
+    pub fn sweep_protocol_fees(ctx: Context<SweepProtocolFees>) -> Result<()> {
+        instructions::sweep_protocol_fees::handler(ctx)
+    }
+
+    pub fn sweep_creator_fees<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepCreatorFees<'info>>,
+    ) -> Result<()> {
+        instructions::sweep_creator_fees::handler(ctx)
+    }
+
+    pub fn sweep_referral_fees(ctx: Context<SweepReferralFees>) -> Result<()> {
+        instructions::sweep_referral_fees::handler(ctx)
+    }
+
+    pub fn create_creator_fee_split(
+        ctx: Context<CreateCreatorFeeSplit>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        instructions::create_creator_fee_split::handler(ctx, recipients)
+    }
+
+    pub fn update_creator_fee_split(
+        ctx: Context<CreatorFeeSplitUpdate>,
+        recipients: Vec<FeeRecipient>,
+    ) -> Result<()> {
+        instructions::update_creator_fee_split::handler(ctx, recipients)
+    }
+
+    pub fn lock_creator_fee_split(ctx: Context<CreatorFeeSplitUpdate>) -> Result<()> {
+        instructions::lock_creator_fee_split::handler(ctx)
+    }
+
     // Admin instructions
     pub fn create_quote_asset_badge(ctx: Context<CreateQuoteAssetBadge>) -> Result<()> {
         instructions::create_quote_asset_badge::handler(ctx)
@@ -279,6 +451,49 @@ pub mod token_mill {
         )
     }
 
+    pub fn create_discount_schedule(
+        ctx: Context<CreateDiscountSchedule>,
+        thresholds: Vec<u64>,
+        discounts_bps: Vec<u16>,
+    ) -> Result<()> {
+        instructions::create_discount_schedule::handler(ctx, thresholds, discounts_bps)
+    }
+
+    pub fn update_discount_schedule(
+        ctx: Context<UpdateDiscountSchedule>,
+        thresholds: Vec<u64>,
+        discounts_bps: Vec<u16>,
+    ) -> Result<()> {
+        instructions::update_discount_schedule::handler(ctx, thresholds, discounts_bps)
+    }
+
+    pub fn update_discount_strategy(
+        ctx: Context<ConfigUpdate>,
+        new_discount_strategy: DiscountStrategy,
+    ) -> Result<()> {
+        instructions::update_discount_strategy::handler(ctx, new_discount_strategy)
+    }
+
+    pub fn update_fee_share_policy(
+        ctx: Context<ConfigUpdate>,
+        new_max_creator_fee_share: u16,
+        new_fee_share_notice_period: i64,
+    ) -> Result<()> {
+        instructions::update_fee_share_policy::handler(
+            ctx,
+            new_max_creator_fee_share,
+            new_fee_share_notice_period,
+        )
+    }
+
+    pub fn update_config_pause(ctx: Context<ConfigUpdate>, paused: bool) -> Result<()> {
+        instructions::update_config_pause::handler(ctx, paused)
+    }
+
+    pub fn update_market_pause(ctx: Context<MarketPauseUpdate>, paused: bool) -> Result<()> {
+        instructions::update_market_pause::handler(ctx, paused)
+    }
+
     pub fn update_protocol_fee_recipient(
         ctx: Context<ConfigUpdate>,
         new_protocol_fee_recipient: Pubkey,
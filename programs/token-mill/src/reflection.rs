@@ -27,15 +27,19 @@ pub struct ClaimReflection<'info> {
 
     pub market_base_mint: AccountInfo<'info>,
 
-    #[account(mut)]
+    #[account(mut, has_one = config @ TokenMillError::InvalidConfigAccount)]
     pub market: AccountLoader<'info, Market>,
 
+    pub config: Account<'info, TokenMillConfig>,
+
     pub owner: Signer<'info>,
 
     pub token_program: Program<'info, TokenInterface>,
 }
 
 pub fn handler(ctx: Context<ClaimReflection>) -> Result<u64> {
+    ctx.accounts.market.load()?.assert_not_paused(&ctx.accounts.config)?;
+
     // Check exclusion
     let excl = &ctx.accounts.exclusion_list;
     for pk in excl.excluded.iter() {
@@ -8,6 +8,18 @@ pub fn assert_pda(seeds: &[&[u8]], program_id: &Pubkey, account: &Pubkey) -> Res
     Ok(())
 }
 
+pub fn assert_deadline(deadline: Option<crate::Deadline>) -> Result<()> {
+    let expired = match deadline {
+        Some(crate::Deadline::UnixTimestamp(timestamp)) => Clock::get()?.unix_timestamp > timestamp,
+        Some(crate::Deadline::Slot(slot)) => Clock::get()?.slot > slot,
+        None => false,
+    };
+    if expired {
+        return Err(error!(crate::errors::TokenMillError::DeadlineExceeded));
+    }
+    Ok(())
+}
+
 pub fn assert_owner(account_info: &AccountInfo, owner: &Pubkey) -> Result<()> {
     if account_info.owner != owner {
         return Err(error!(crate::errors::TokenMillError::InvalidAuthority));
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::{constant::BPS_DENOMINATOR, errors::TokenMillError};
+
 #[account]
 #[derive(InitSpace)]
 pub struct TokenMillConfig {
@@ -12,4 +14,45 @@ pub struct TokenMillConfig {
     pub cpi_whitelist: Vec<Pubkey>,
     // Maximum number of remaining accounts allowed when forwarding an instruction
     pub max_forwarded_accounts: u8,
+    // Highest creator fee share a market can set
+    pub max_creator_fee_share: u16,
+    // Delay in seconds before fee share changes take effect on a live market
+    pub fee_share_notice_period: i64,
+    // Amount mapped to the discount schedule tiers on lamport trades and swaps
+    pub discount_strategy: DiscountStrategy,
+    // Emergency pause of every market of the config
+    pub paused: bool,
+}
+
+/// Source of the amount a trader is ranked by in the discount schedule.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum DiscountStrategy {
+    /// Rolling volume of the trader's `TraderStats`, in lamports
+    TradingVolume,
+    /// Balance of `mint` held in a token account owned by the trader
+    TokenHolding { mint: Pubkey },
+    /// Amount staked by the trader on `market`
+    StakedBalance { market: Pubkey },
+}
+
+impl TokenMillConfig {
+    /// Checks market fee shares against the protocol share and the creator share cap.
+    pub fn validate_market_fee_shares(
+        &self,
+        creator_fee_share: u16,
+        staking_fee_share: u16,
+    ) -> Result<()> {
+        if creator_fee_share > self.max_creator_fee_share {
+            return Err(error!(TokenMillError::InvalidFeeShare));
+        }
+
+        let total_fee_share = creator_fee_share as u32
+            + staking_fee_share as u32
+            + self.default_protocol_fee_share as u32;
+        if total_fee_share != BPS_DENOMINATOR as u32 {
+            return Err(error!(TokenMillError::InvalidFeeShare));
+        }
+
+        Ok(())
+    }
 }
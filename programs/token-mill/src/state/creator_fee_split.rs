@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::BPS_DENOMINATOR, errors::TokenMillError};
+
+pub const CREATOR_FEE_SPLIT_PDA_SEED: &str = "creator_fee_split";
+pub const MAX_FEE_SPLIT_RECIPIENTS: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct FeeRecipient {
+    pub recipient: Pubkey,
+    pub weight_bps: u16,
+}
+
+/// Recipients sharing the creator fees of a market, replacing `Market::creator` as the payee
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorFeeSplit {
+    pub market: Pubkey,
+    pub bump: u8,
+    pub is_locked: bool,
+    #[max_len(MAX_FEE_SPLIT_RECIPIENTS)]
+    pub recipients: Vec<FeeRecipient>,
+}
+
+impl CreatorFeeSplit {
+    /// Replaces the recipients, whose weights must be non-zero and add up to `BPS_DENOMINATOR`.
+    pub fn set_recipients(&mut self, recipients: Vec<FeeRecipient>) -> Result<()> {
+        if recipients.is_empty() || recipients.len() > MAX_FEE_SPLIT_RECIPIENTS {
+            return Err(error!(TokenMillError::InvalidCreatorFeeSplit));
+        }
+
+        let mut total_weight = 0u32;
+        for (i, recipient) in recipients.iter().enumerate() {
+            if recipient.weight_bps == 0
+                || recipients[..i]
+                    .iter()
+                    .any(|other| other.recipient == recipient.recipient)
+            {
+                return Err(error!(TokenMillError::InvalidCreatorFeeSplit));
+            }
+            total_weight += recipient.weight_bps as u32;
+        }
+        if total_weight != BPS_DENOMINATOR as u32 {
+            return Err(error!(TokenMillError::InvalidCreatorFeeSplit));
+        }
+
+        self.recipients = recipients;
+
+        Ok(())
+    }
+
+    /// Shares of `amount` owed to each recipient, in order. The rounding dust goes to the first
+    /// recipient.
+    pub fn split(&self, amount: u64) -> Vec<u64> {
+        let mut shares: Vec<u64> = self
+            .recipients
+            .iter()
+            .map(|recipient| {
+                (amount as u128 * recipient.weight_bps as u128 / BPS_DENOMINATOR as u128) as u64
+            })
+            .collect();
+        let distributed: u64 = shares.iter().sum();
+        if let Some(first) = shares.first_mut() {
+            *first += amount - distributed;
+        }
+
+        shares
+    }
+}
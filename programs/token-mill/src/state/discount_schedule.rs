@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{constant::MAX_DISCOUNT_BPS, errors::TokenMillError};
+
+pub const DISCOUNT_SCHEDULE_PDA_SEED: &str = "discount_schedule";
+pub const MAX_DISCOUNT_TIERS: usize = 8;
+
+/// Protocol fee discount tiers of a config, from the lowest threshold to the highest
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct DiscountSchedule {
+    pub config: Pubkey,
+    pub bump: u8,
+    pub tier_count: u8,
+    /// Amount from which each tier applies, measured as set by the config's `DiscountStrategy`
+    pub thresholds: [u64; MAX_DISCOUNT_TIERS],
+    /// Discount of each tier on the protocol fee, in basis points
+    pub discounts_bps: [u16; MAX_DISCOUNT_TIERS],
+}
+
+impl DiscountSchedule {
+    /// Replaces the tiers, which must be sorted by strictly increasing threshold with
+    /// non-decreasing discounts capped at `MAX_DISCOUNT_BPS`.
+    pub fn set_tiers(&mut self, thresholds: &[u64], discounts_bps: &[u16]) -> Result<()> {
+        if thresholds.len() != discounts_bps.len() || thresholds.len() > MAX_DISCOUNT_TIERS {
+            return Err(error!(TokenMillError::InvalidDiscountSchedule));
+        }
+        for i in 0..thresholds.len() {
+            if discounts_bps[i] > MAX_DISCOUNT_BPS {
+                return Err(error!(TokenMillError::InvalidDiscountSchedule));
+            }
+            if i > 0
+                && (thresholds[i] <= thresholds[i - 1] || discounts_bps[i] < discounts_bps[i - 1])
+            {
+                return Err(error!(TokenMillError::InvalidDiscountSchedule));
+            }
+        }
+
+        self.tier_count = thresholds.len() as u8;
+        self.thresholds = [0; MAX_DISCOUNT_TIERS];
+        self.discounts_bps = [0; MAX_DISCOUNT_TIERS];
+        self.thresholds[..thresholds.len()].copy_from_slice(thresholds);
+        self.discounts_bps[..discounts_bps.len()].copy_from_slice(discounts_bps);
+
+        Ok(())
+    }
+
+    /// Discount of the highest tier reached by `amount`, in basis points.
+    pub fn discount_bps(&self, amount: u64) -> u16 {
+        (0..self.tier_count as usize)
+            .rev()
+            .find(|&i| amount >= self.thresholds[i])
+            .map_or(0, |i| self.discounts_bps[i])
+    }
+}
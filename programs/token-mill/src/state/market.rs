@@ -1,21 +1,202 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
 
-use crate::constant::PRICES_LENGTH;
+use crate::{
+    constant::{BPS_DENOMINATOR, MAX_PRICE, MILL_TOKEN_DECIMALS, PRICES_LENGTH},
+    curve::{exponential, math::mul_div},
+    errors::TokenMillError,
+    state::{TokenMillConfig, WalletPosition},
+    CurveParams, LaunchFeeDecay, LaunchFeeRecipient, SwapType,
+};
 
 pub const MARKET_PDA_SEED: &str = "market";
 
+/// Layout of the markets created by this version of the program. Version 0 markets end at
+/// `Market::layout_version` and are grown by `migrate_market_layout`
+pub const MARKET_LAYOUT_VERSION: u8 = 1;
+
+/// Curve family of a market, stored as `Market::curve_kind`
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(u8)]
+pub enum CurveKind {
+    PriceArray = 0,
+    Exponential = 1,
+    ConstantProduct = 2,
+}
+
+impl TryFrom<u8> for CurveKind {
+    type Error = anchor_lang::error::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(CurveKind::PriceArray),
+            1 => Ok(CurveKind::Exponential),
+            2 => Ok(CurveKind::ConstantProduct),
+            _ => Err(error!(TokenMillError::InvalidMarketState)),
+        }
+    }
+}
+
 #[zero_copy]
 #[derive(Debug, InitSpace)]
 pub struct MarketFees {
     /// staking_fee_share + creator_fee_share + protocol_fee_share = 100%
     pub staking_fee_share: u16,
     pub creator_fee_share: u16,
-    _space: u32,
+    /// Shares replacing the current ones from `MarketFeeVault::scheduled_fee_shares_at` on
+    pub scheduled_staking_fee_share: u16,
+    pub scheduled_creator_fee_share: u16,
 
     pub pending_staking_fees: u64,
     pub pending_creator_fees: u64,
 }
 
+/// Lamport fees held by a market and the schedule of its fee shares, appended by layout
+/// version 1
+#[zero_copy]
+#[derive(Debug, InitSpace)]
+pub struct MarketFeeVault {
+    /// Unix timestamp at which the scheduled shares take effect, 0 when none are scheduled
+    pub scheduled_fee_shares_at: i64,
+
+    /// Lamport fees of `buy` and `sell` held by the market PDA until swept to their recipients
+    pub pending_protocol_lamports: u64,
+    pub pending_creator_lamports: u64,
+    /// Backs the `pending_lamports` credited to referral accounts, swept to them on demand
+    pub pending_referral_lamports: u64,
+
+    /// Launch fees routed to buybacks, in lamports for `buy` and in quote tokens for `swap`,
+    /// spent by `perform_buyback`
+    pub pending_buyback_lamports: u64,
+    pub pending_buyback_fees: u64,
+}
+
+impl MarketFeeVault {
+    /// Lamports held by the market PDA on behalf of fee recipients, not available to sellers.
+    pub fn pending_lamports(&self) -> Result<u64> {
+        self.pending_protocol_lamports
+            .checked_add(self.pending_creator_lamports)
+            .and_then(|lamports| lamports.checked_add(self.pending_referral_lamports))
+            .and_then(|lamports| lamports.checked_add(self.pending_buyback_lamports))
+            .ok_or(error!(TokenMillError::MathOverflow))
+    }
+}
+
+/// Anti-sniper fee charged on top of buys, decaying linearly from `start_fee_bps` at the first
+/// trade to 0 once `duration` slots or seconds have elapsed
+#[zero_copy]
+#[derive(Debug, InitSpace)]
+pub struct LaunchFee {
+    /// 0 when the market has no launch fee
+    pub start_fee_bps: u16,
+    /// 0 when `duration` is counted in slots, 1 in seconds
+    pub decay_in_seconds: u8,
+    /// 0 to accrue the fee to the creator, 1 to the buyback pool
+    pub to_buyback: u8,
+    _space: [u8; 4],
+    pub duration: u64,
+    /// Slot or unix timestamp of the first trade, 0 before it
+    pub started_at: u64,
+}
+
+impl LaunchFee {
+    pub fn new(start_fee_bps: u16, decay: LaunchFeeDecay, recipient: LaunchFeeRecipient) -> Self {
+        let (decay_in_seconds, duration) = match decay {
+            LaunchFeeDecay::Slots(slots) => (0, slots),
+            LaunchFeeDecay::Seconds(seconds) => (1, seconds),
+        };
+
+        LaunchFee {
+            start_fee_bps,
+            decay_in_seconds,
+            to_buyback: (recipient == LaunchFeeRecipient::Buyback) as u8,
+            _space: [0; 4],
+            duration,
+            started_at: 0,
+        }
+    }
+
+    fn now(&self, clock: &Clock) -> u64 {
+        if self.decay_in_seconds != 0 {
+            clock.unix_timestamp as u64
+        } else {
+            clock.slot
+        }
+    }
+
+    /// Launch fee in effect at `clock`, in basis points. The first trade pays the full fee.
+    pub fn fee_bps(&self, clock: &Clock) -> u16 {
+        if self.start_fee_bps == 0 || self.started_at == 0 {
+            return self.start_fee_bps;
+        }
+
+        let elapsed = self.now(clock).saturating_sub(self.started_at);
+        if elapsed >= self.duration {
+            return 0;
+        }
+
+        (self.start_fee_bps as u128 * (self.duration - elapsed) as u128 / self.duration as u128)
+            as u16
+    }
+
+    /// Starts the decay on the first trade.
+    pub fn start(&mut self, clock: &Clock) {
+        if self.start_fee_bps != 0 && self.started_at == 0 {
+            self.started_at = self.now(clock);
+        }
+    }
+}
+
+/// Caps on buys before migration, 0 for no limit
+#[zero_copy]
+#[derive(Debug, InitSpace)]
+pub struct BuyLimits {
+    /// Base tokens bought by a single trade
+    pub max_base_per_transaction: u64,
+    /// Base tokens held by a single wallet, tracked by its `WalletPosition`
+    pub max_base_per_wallet: u64,
+}
+
+/// Opening schedule of a market. Before `open_at`, only the wallets of the presale allow-list
+/// can trade
+#[zero_copy]
+#[derive(Debug, InitSpace)]
+pub struct LaunchWindow {
+    /// Unix timestamp from which anyone can trade, 0 to open at creation
+    pub open_at: i64,
+    /// Merkle root of the presale allow-list, all zeros without presale
+    pub presale_root: [u8; 32],
+    /// Base tokens a wallet can buy during the presale, 0 for no limit
+    pub presale_max_base_per_wallet: u64,
+}
+
+impl LaunchWindow {
+    pub fn has_presale(&self) -> bool {
+        self.presale_root != [0; 32]
+    }
+
+    pub fn is_open(&self, timestamp: i64) -> bool {
+        timestamp >= self.open_at
+    }
+}
+
+/// Parameters of the exponential and constant-product curves, unused by the price-array curve
+#[zero_copy]
+#[derive(Debug, InitSpace)]
+pub struct MarketCurve {
+    /// Exponential curve prices at zero and full supply, in quote units per whole base token
+    pub initial_price: u64,
+    pub final_price: u64,
+
+    /// Constant-product virtual reserves at zero circulating supply
+    pub virtual_base_reserve: u64,
+    pub virtual_quote_reserve: u64,
+
+    /// Ask premium over the bid side of the curve
+    pub spread_bps: u16,
+    _space: [u8; 6],
+}
+
 #[account(zero_copy)]
 #[derive(Debug, InitSpace)]
 pub struct Market {
@@ -25,6 +206,7 @@ pub struct Market {
     pub base_token_mint: Pubkey,
     pub quote_token_mint: Pubkey,
 
+    /// Base tokens held by the market and available to buyers
     pub base_reserve: u64,
 
     pub bid_prices: [u64; PRICES_LENGTH],
@@ -42,5 +224,277 @@ pub struct Market {
     pub is_migrated: u8,
     pub mint_revoked: u8,
     pub freeze_revoked: u8,
-    _space: [u8; 2],
+    pub curve_kind: u8,
+    /// `MARKET_LAYOUT_VERSION` of the account, the fields below are appended by version 1
+    pub layout_version: u8,
+
+    /// Base tokens held outside of the market, bounded by `total_supply`
+    pub circulating_supply: u64,
+
+    pub curve: MarketCurve,
+    pub fee_vault: MarketFeeVault,
+    pub launch_fee: LaunchFee,
+    pub buy_limits: BuyLimits,
+    pub launch_window: LaunchWindow,
+    /// Incremented by `free_market`, swap authority badges issued under an earlier epoch are stale
+    pub swap_authority_epoch: u64,
+
+    pub has_creator_fee_split: u8,
+    pub is_paused: u8,
+    _space: [u8; 6],
+}
+
+impl Market {
+    pub fn curve_kind(&self) -> Result<CurveKind> {
+        CurveKind::try_from(self.curve_kind)
+    }
+
+    /// Returns the `(creator_fee_share, staking_fee_share)` in effect at `timestamp`.
+    pub fn fee_shares_at(&self, timestamp: i64) -> (u16, u16) {
+        let scheduled_at = self.fee_vault.scheduled_fee_shares_at;
+        if scheduled_at != 0 && timestamp >= scheduled_at {
+            (
+                self.fees.scheduled_creator_fee_share,
+                self.fees.scheduled_staking_fee_share,
+            )
+        } else {
+            (self.fees.creator_fee_share, self.fees.staking_fee_share)
+        }
+    }
+
+    /// Schedules new fee shares, replacing any change that has not taken effect yet.
+    pub fn schedule_fee_shares(
+        &mut self,
+        creator_fee_share: u16,
+        staking_fee_share: u16,
+        timestamp: i64,
+        effective_at: i64,
+    ) {
+        let (current_creator_fee_share, current_staking_fee_share) = self.fee_shares_at(timestamp);
+        self.fees.creator_fee_share = current_creator_fee_share;
+        self.fees.staking_fee_share = current_staking_fee_share;
+
+        if effective_at <= timestamp {
+            self.fees.creator_fee_share = creator_fee_share;
+            self.fees.staking_fee_share = staking_fee_share;
+            self.fee_vault.scheduled_fee_shares_at = 0;
+        } else {
+            self.fees.scheduled_creator_fee_share = creator_fee_share;
+            self.fees.scheduled_staking_fee_share = staking_fee_share;
+            self.fee_vault.scheduled_fee_shares_at = effective_at;
+        }
+    }
+
+    /// Fails while the market or its whole config is paused.
+    pub fn assert_not_paused(&self, config: &TokenMillConfig) -> Result<()> {
+        if self.is_paused != 0 || config.paused {
+            return Err(error!(TokenMillError::MarketPaused));
+        }
+
+        Ok(())
+    }
+
+    /// Credits a launch fee to its recipient, in lamports for `buy` and in quote tokens for
+    /// `swap`.
+    pub fn accrue_launch_fee(&mut self, launch_fee: u64, in_lamports: bool) -> Result<()> {
+        let pending = match (self.launch_fee.to_buyback != 0, in_lamports) {
+            (false, true) => &mut self.fee_vault.pending_creator_lamports,
+            (false, false) => &mut self.fees.pending_creator_fees,
+            (true, true) => &mut self.fee_vault.pending_buyback_lamports,
+            (true, false) => &mut self.fee_vault.pending_buyback_fees,
+        };
+        *pending = pending
+            .checked_add(launch_fee)
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+
+        Ok(())
+    }
+
+    /// Enforces the opening schedule and the buy limits of the market on a trade of
+    /// `base_amount` base tokens and tracks it in the trader's `wallet_position`, which is
+    /// required before `open_at` and on buys when the market caps wallets. Presale buys are
+    /// capped by the presale limit instead of the wallet limit. The limits are lifted once the
+    /// market is migrated.
+    pub fn record_position_trade(
+        &self,
+        swap_type: SwapType,
+        base_amount: u64,
+        wallet_position: Option<&mut WalletPosition>,
+        timestamp: i64,
+    ) -> Result<()> {
+        let is_open = self.launch_window.is_open(timestamp);
+        if !is_open
+            && !wallet_position
+                .as_ref()
+                .is_some_and(|wallet_position| wallet_position.is_allow_listed)
+        {
+            return Err(error!(TokenMillError::MarketNotOpen));
+        }
+
+        if self.is_migrated != 0 {
+            return Ok(());
+        }
+
+        match swap_type {
+            SwapType::Buy => {
+                let max_per_transaction = self.buy_limits.max_base_per_transaction;
+                if max_per_transaction != 0 && base_amount > max_per_transaction {
+                    return Err(error!(TokenMillError::BuyLimitExceeded));
+                }
+
+                let max_per_wallet = if is_open {
+                    self.buy_limits.max_base_per_wallet
+                } else {
+                    self.launch_window.presale_max_base_per_wallet
+                };
+                match wallet_position {
+                    Some(wallet_position) => {
+                        wallet_position.record_buy(base_amount, max_per_wallet)?
+                    }
+                    None if max_per_wallet != 0 => {
+                        return Err(error!(TokenMillError::InvalidWalletPosition))
+                    }
+                    None => {}
+                }
+            }
+            SwapType::Sell => {
+                if let Some(wallet_position) = wallet_position {
+                    wallet_position.record_sell(base_amount);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the market is quoted in lamports and traded through `buy` and `sell`.
+    pub fn is_lamport_market(&self) -> bool {
+        self.quote_token_mint == native_mint::ID
+    }
+
+    /// Takes `amount` out of the launch fees routed to the buyback pool to fund a buyback, in
+    /// lamports on lamport markets and in quote tokens otherwise.
+    pub fn spend_buyback_fees(&mut self, amount: u64) -> Result<()> {
+        let pending = if self.is_lamport_market() {
+            &mut self.fee_vault.pending_buyback_lamports
+        } else {
+            &mut self.fee_vault.pending_buyback_fees
+        };
+        *pending = pending
+            .checked_sub(amount)
+            .ok_or(error!(TokenMillError::InvalidAmount))?;
+
+        Ok(())
+    }
+
+    /// Records the curve family picked at creation. Price-array markets get their prices later
+    /// through `set_market_prices`.
+    pub fn set_curve(
+        &mut self,
+        curve: CurveParams,
+        total_supply: u64,
+        quote_token_decimals: u8,
+    ) -> Result<()> {
+        let max_price = (MAX_PRICE as u128)
+            .checked_mul(10u128.pow(quote_token_decimals as u32))
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+
+        let (curve_kind, curve) = match curve {
+            CurveParams::PriceArray => (
+                CurveKind::PriceArray,
+                MarketCurve {
+                    initial_price: 0,
+                    final_price: 0,
+                    virtual_base_reserve: 0,
+                    virtual_quote_reserve: 0,
+                    spread_bps: 0,
+                    _space: [0; 6],
+                },
+            ),
+            CurveParams::Exponential {
+                initial_price,
+                final_price,
+                spread_bps,
+            } => {
+                exponential::growth_rate(initial_price, final_price)?;
+                if final_price as u128 > max_price {
+                    return Err(error!(TokenMillError::PriceTooHigh));
+                }
+
+                (
+                    CurveKind::Exponential,
+                    MarketCurve {
+                        initial_price,
+                        final_price,
+                        virtual_base_reserve: 0,
+                        virtual_quote_reserve: 0,
+                        spread_bps,
+                        _space: [0; 6],
+                    },
+                )
+            }
+            CurveParams::ConstantProduct {
+                virtual_base_reserve,
+                virtual_quote_reserve,
+                spread_bps,
+            } => {
+                // The virtual base reserve must outlast the whole supply
+                if virtual_quote_reserve == 0 || virtual_base_reserve <= total_supply {
+                    return Err(error!(TokenMillError::InvalidCurveParameters));
+                }
+                let remaining_base = (virtual_base_reserve - total_supply) as u128;
+                let final_price = mul_div(
+                    virtual_base_reserve as u128 * virtual_quote_reserve as u128,
+                    10u128.pow(MILL_TOKEN_DECIMALS as u32),
+                    remaining_base * remaining_base,
+                    true,
+                )?;
+                if final_price > max_price {
+                    return Err(error!(TokenMillError::PriceTooHigh));
+                }
+
+                (
+                    CurveKind::ConstantProduct,
+                    MarketCurve {
+                        initial_price: 0,
+                        final_price: 0,
+                        virtual_base_reserve,
+                        virtual_quote_reserve,
+                        spread_bps,
+                        _space: [0; 6],
+                    },
+                )
+            }
+        };
+        if curve.spread_bps > BPS_DENOMINATOR {
+            return Err(error!(TokenMillError::InvalidCurveParameters));
+        }
+
+        self.curve_kind = curve_kind as u8;
+        self.curve = curve;
+        Ok(())
+    }
+
+    /// Records base tokens leaving the market, rejecting anything above the hard cap.
+    pub fn increase_circulating_supply(&mut self, amount: u64) -> Result<()> {
+        let circulating_supply = self
+            .circulating_supply
+            .checked_add(amount)
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+        if circulating_supply > self.total_supply {
+            return Err(error!(TokenMillError::InvalidTotalSupply));
+        }
+
+        self.circulating_supply = circulating_supply;
+        Ok(())
+    }
+
+    /// Records base tokens coming back to the market, either into the reserve or burned.
+    pub fn decrease_circulating_supply(&mut self, amount: u64) -> Result<()> {
+        self.circulating_supply = self
+            .circulating_supply
+            .checked_sub(amount)
+            .ok_or(error!(TokenMillError::InvalidAmount))?;
+        Ok(())
+    }
 }
@@ -1,4 +1,6 @@
 pub mod config;
+pub mod creator_fee_split;
+pub mod discount_schedule;
 pub mod market;
 pub mod quote_token_badge;
 pub mod referral;
@@ -8,8 +10,12 @@ pub mod vesting;
 pub mod reflection;
 pub mod dao;
 pub mod airdrop;
+pub mod trader_stats;
+pub mod wallet_position;
 
 pub use config::*;
+pub use creator_fee_split::*;
+pub use discount_schedule::*;
 pub use market::*;
 pub use quote_token_badge::*;
 pub use referral::*;
@@ -19,3 +25,5 @@ pub use vesting::*;
 pub use reflection::*;
 pub use dao::*;
 pub use airdrop::*;
+pub use trader_stats::*;
+pub use wallet_position::*;
@@ -1,9 +1,105 @@
 use anchor_lang::prelude::*;
 
+use crate::{errors::TokenMillError, SwapType};
+
 pub const SWAP_AUTHORITY_BADGE_PDA_SEED: &str = "swap_authority";
 
 #[account]
 #[derive(Debug, InitSpace)]
 pub struct SwapAuthorityBadge {
     pub bump: u8,
+    pub scope: SwapAuthorityScope,
+    /// `swap_authority_epoch` of the market when the badge was issued
+    pub epoch: u64,
+    /// Whether the badge was issued by `lock_market`, letting its holder free the market
+    pub is_lock_authority: bool,
+    /// Start of the current volume window, as a unix timestamp
+    pub window_start: i64,
+    /// Base tokens traded by the swap authority since `window_start`
+    pub window_volume: u64,
+}
+
+/// Trading rights granted to a swap authority on a permissioned market.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct SwapAuthorityScope {
+    /// Unix timestamp from which the badge can no longer trade, 0 for no expiry
+    pub expires_at: i64,
+    pub directions: SwapDirections,
+    /// Base tokens the swap authority can trade per window, 0 for no cap
+    pub volume_cap: u64,
+    /// Length of a volume window in seconds, required with a volume cap
+    pub volume_window: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum SwapDirections {
+    BuyOnly,
+    SellOnly,
+    Both,
+}
+
+impl SwapAuthorityScope {
+    pub fn validate(&self) -> Result<()> {
+        if self.volume_cap != 0 && self.volume_window <= 0 {
+            return Err(error!(TokenMillError::InvalidSwapAuthorityScope));
+        }
+
+        Ok(())
+    }
+}
+
+impl SwapAuthorityBadge {
+    /// Fails if the badge has expired or the market has been freed since it was issued.
+    pub fn assert_active(&self, market_epoch: u64, timestamp: i64) -> Result<()> {
+        let expires_at = self.scope.expires_at;
+
+        if self.epoch != market_epoch || (expires_at != 0 && timestamp >= expires_at) {
+            return Err(error!(TokenMillError::SwapAuthorityExpired));
+        }
+
+        Ok(())
+    }
+
+    /// Checks a trade of `base_amount` base tokens against the scope of the badge and adds it
+    /// to the volume of the current window.
+    pub fn record_trade(
+        &mut self,
+        swap_type: SwapType,
+        base_amount: u64,
+        market_epoch: u64,
+        timestamp: i64,
+    ) -> Result<()> {
+        self.assert_active(market_epoch, timestamp)?;
+
+        let scope = self.scope;
+
+        let is_allowed = match (scope.directions, swap_type) {
+            (SwapDirections::Both, _)
+            | (SwapDirections::BuyOnly, SwapType::Buy)
+            | (SwapDirections::SellOnly, SwapType::Sell) => true,
+            _ => false,
+        };
+        if !is_allowed {
+            return Err(error!(TokenMillError::SwapDirectionNotAllowed));
+        }
+
+        if scope.volume_cap == 0 {
+            return Ok(());
+        }
+
+        if timestamp >= self.window_start.saturating_add(scope.volume_window) {
+            self.window_start = timestamp;
+            self.window_volume = 0;
+        }
+
+        self.window_volume = self
+            .window_volume
+            .checked_add(base_amount)
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+        if self.window_volume > scope.volume_cap {
+            return Err(error!(TokenMillError::SwapVolumeCapExceeded));
+        }
+
+        Ok(())
+    }
 }
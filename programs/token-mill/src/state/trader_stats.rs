@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::TokenMillError;
+
+pub const TRADER_STATS_PDA_SEED: &str = "trader_stats";
+pub const VOLUME_WINDOW_DAYS: usize = 30;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Rolling lamport volume of a trader, recorded by `buy` and `sell`. Swaps are quoted in SPL
+/// tokens with no common lamport value, so they don't add to it.
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct TraderStats {
+    pub config: Pubkey,
+    pub user: Pubkey,
+    pub bump: u8,
+    /// Day of the most recent trade, in days since the unix epoch
+    pub last_trade_day: i64,
+    /// Quote volume traded each day, indexed by day modulo `VOLUME_WINDOW_DAYS`
+    pub daily_volumes: [u64; VOLUME_WINDOW_DAYS],
+}
+
+impl TraderStats {
+    /// Quote volume traded over the `VOLUME_WINDOW_DAYS` days ending at `timestamp`.
+    pub fn rolling_volume(&self, timestamp: i64) -> u64 {
+        let day = timestamp.div_euclid(SECONDS_PER_DAY);
+        let window_start = day - VOLUME_WINDOW_DAYS as i64 + 1;
+
+        (0..VOLUME_WINDOW_DAYS as i64)
+            .map(|age| self.last_trade_day - age)
+            .filter(|trade_day| *trade_day >= window_start && *trade_day <= day)
+            .fold(0u64, |volume, trade_day| {
+                volume.saturating_add(self.daily_volumes[Self::bucket(trade_day)])
+            })
+    }
+
+    /// Adds `quote_amount` to the volume of the day of `timestamp`.
+    pub fn record_volume(&mut self, quote_amount: u64, timestamp: i64) -> Result<()> {
+        let day = timestamp.div_euclid(SECONDS_PER_DAY);
+
+        // Clear the buckets of the days without trades since the last one
+        if day > self.last_trade_day {
+            let elapsed_days = (day - self.last_trade_day).min(VOLUME_WINDOW_DAYS as i64);
+            for age in 0..elapsed_days {
+                self.daily_volumes[Self::bucket(day - age)] = 0;
+            }
+            self.last_trade_day = day;
+        }
+
+        let bucket = Self::bucket(self.last_trade_day);
+        self.daily_volumes[bucket] = self.daily_volumes[bucket]
+            .checked_add(quote_amount)
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+
+        Ok(())
+    }
+
+    fn bucket(day: i64) -> usize {
+        day.rem_euclid(VOLUME_WINDOW_DAYS as i64) as usize
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::TokenMillError;
+
+pub const WALLET_POSITION_PDA_SEED: &str = "wallet_position";
+
+#[account]
+#[derive(Debug, InitSpace)]
+pub struct WalletPosition {
+    pub market: Pubkey,
+    pub user: Pubkey,
+    pub bump: u8,
+    /// Whether the user proved membership of the market's presale allow-list
+    pub is_allow_listed: bool,
+    /// Base tokens bought minus base tokens sold by the user before migration
+    pub base_amount: u64,
+}
+
+impl WalletPosition {
+    /// Adds a buy to the position, failing if it grows past `max_base_amount` (0 for no limit).
+    pub fn record_buy(&mut self, base_amount: u64, max_base_amount: u64) -> Result<()> {
+        self.base_amount = self
+            .base_amount
+            .checked_add(base_amount)
+            .ok_or(error!(TokenMillError::MathOverflow))?;
+
+        if max_base_amount != 0 && self.base_amount > max_base_amount {
+            return Err(error!(TokenMillError::BuyLimitExceeded));
+        }
+
+        Ok(())
+    }
+
+    /// Removes a sell from the position. Tokens received from other wallets are not tracked, so
+    /// the position never goes below 0.
+    pub fn record_sell(&mut self, base_amount: u64) {
+        self.base_amount = self.base_amount.saturating_sub(base_amount);
+    }
+}
@@ -0,0 +1,85 @@
+//! Token-2022 extension support for the mints traded by markets.
+//!
+//! Mints with a `TransferFeeConfig` withhold part of every transfer, so the amount received is
+//! lower than the amount sent. The fee of the current epoch is applied to every transfer
+//! leg of a swap so the market only accounts for what it actually receives.
+
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token_2022::{
+        spl_token_2022::{
+            extension::{
+                transfer_fee::{TransferFee, TransferFeeConfig},
+                BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+            },
+            state::Mint as MintState,
+        },
+        Token2022,
+    },
+    token_interface::Mint,
+};
+
+use crate::errors::TokenMillError;
+
+/// Extensions a traded mint may carry. Anything else (transfer hooks, permanent delegates,
+/// confidential transfers, ...) could move or freeze market funds behind its back.
+const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 3] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+];
+
+pub fn assert_supported_mint(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    assert_supported_mint_info(&mint.to_account_info())
+}
+
+/// `assert_supported_mint` for mints not deserialized as an `InterfaceAccount`.
+pub fn assert_supported_mint_info(mint_info: &AccountInfo) -> Result<()> {
+    if *mint_info.owner != Token2022::id() {
+        return Ok(());
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    for extension in mint_state.get_extension_types()? {
+        if !SUPPORTED_MINT_EXTENSIONS.contains(&extension) {
+            return Err(error!(TokenMillError::UnsupportedTokenMint));
+        }
+    }
+
+    Ok(())
+}
+
+fn epoch_transfer_fee(mint: &InterfaceAccount<Mint>) -> Result<Option<TransferFee>> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != Token2022::id() {
+        return Ok(None);
+    }
+
+    let data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => Ok(Some(*config.get_epoch_fee(Clock::get()?.epoch))),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Amount received when `amount` is sent.
+pub fn get_amount_after_fee(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    match epoch_transfer_fee(mint)? {
+        Some(transfer_fee) => transfer_fee
+            .calculate_post_fee_amount(amount)
+            .ok_or(error!(TokenMillError::MathOverflow)),
+        None => Ok(amount),
+    }
+}
+
+/// Amount to send for `amount` to be received.
+pub fn get_amount_before_fee(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    match epoch_transfer_fee(mint)? {
+        Some(transfer_fee) if amount > 0 => transfer_fee
+            .calculate_pre_fee_amount(amount)
+            .ok_or(error!(TokenMillError::MathOverflow)),
+        _ => Ok(amount),
+    }
+}